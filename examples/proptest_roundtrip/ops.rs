@@ -0,0 +1,155 @@
+//! A model of `BinaryWriter` operations, for round-trip property testing with `proptest`.
+//!
+//! Each `Op` is a value along with the method used to encode it. `write_op` encodes an `Op` and
+//! `check_op` decodes it and checks that the decoded value is the same. 7-bit encoded integers are
+//! read with the strict readers, because `BinaryWriter` always writes the shortest encoding. The model covers the
+//! primitive types, strings, 7-bit encoded integers, and `decimal` bits.
+//!
+//! This module is used by the `proptest_roundtrip` example and by the crate's own tests. The
+//! parent module must import `BinaryReader`, `BinaryWriter`, and `BinaryWriterError`.
+
+use super::{BinaryReader, BinaryWriter, BinaryWriterError};
+use proptest::prelude::*;
+
+/// A single value to be encoded, along with the method used to encode it.
+#[derive(Clone, Debug)]
+pub enum Op {
+    U8(u8),
+    I8(i8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    Char(char),
+    Int7I32(i32),
+    Int7I64(i64),
+    Utf8(String),
+    Utf16(String),
+    Utf16Wchars(Vec<u16>),
+    Bytes(Vec<u8>),
+    Decimal([i32; 4]),
+}
+
+/// Generates arbitrary `Op` values.
+pub fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        any::<u8>().prop_map(Op::U8),
+        any::<i8>().prop_map(Op::I8),
+        any::<u16>().prop_map(Op::U16),
+        any::<u32>().prop_map(Op::U32),
+        any::<u64>().prop_map(Op::U64),
+        any::<i16>().prop_map(Op::I16),
+        any::<i32>().prop_map(Op::I32),
+        any::<i64>().prop_map(Op::I64),
+        any::<f32>().prop_map(Op::F32),
+        any::<f64>().prop_map(Op::F64),
+        any::<bool>().prop_map(Op::Bool),
+        any::<char>().prop_map(Op::Char),
+        any::<i32>().prop_map(Op::Int7I32),
+        any::<i64>().prop_map(Op::Int7I64),
+        any::<String>().prop_map(Op::Utf8),
+        any::<String>().prop_map(Op::Utf16),
+        proptest::collection::vec(any::<u16>(), 0..16).prop_map(Op::Utf16Wchars),
+        proptest::collection::vec(any::<u8>(), 0..300).prop_map(Op::Bytes),
+        any::<[i32; 4]>().prop_map(Op::Decimal),
+    ]
+}
+
+/// Encodes `op`.
+pub fn write_op(w: &mut BinaryWriter, op: &Op) -> Result<(), BinaryWriterError> {
+    match op {
+        Op::U8(x) => {
+            w.write_u8(*x);
+        }
+        Op::I8(x) => {
+            w.write_i8(*x);
+        }
+        Op::U16(x) => {
+            w.write_u16(*x);
+        }
+        Op::U32(x) => {
+            w.write_u32(*x);
+        }
+        Op::U64(x) => {
+            w.write_u64(*x);
+        }
+        Op::I16(x) => {
+            w.write_i16(*x);
+        }
+        Op::I32(x) => {
+            w.write_i32(*x);
+        }
+        Op::I64(x) => {
+            w.write_i64(*x);
+        }
+        Op::F32(x) => {
+            w.write_f32(*x);
+        }
+        Op::F64(x) => {
+            w.write_f64(*x);
+        }
+        Op::Bool(x) => {
+            w.write_bool(*x);
+        }
+        Op::Char(x) => {
+            w.write_char(*x);
+        }
+        Op::Int7I32(x) => {
+            w.write_7bit_encoded_i32(*x);
+        }
+        Op::Int7I64(x) => {
+            w.write_7bit_encoded_i64(*x);
+        }
+        Op::Utf8(s) => w.write_utf8_str(s)?,
+        Op::Utf16(s) => w.write_utf16_encode(s)?,
+        Op::Utf16Wchars(s) => w.write_utf16_wchars(s)?,
+        Op::Bytes(b) => w.write_length_prefixed_bytes(b)?,
+        Op::Decimal(bits) => {
+            w.write_decimal_bits(*bits);
+        }
+    }
+    Ok(())
+}
+
+/// Decodes the next value from `r` and checks that it is the value that `op` encoded.
+pub fn check_op(r: &mut BinaryReader<'_>, op: &Op) -> Result<(), TestCaseError> {
+    match op {
+        Op::U8(x) => prop_assert_eq!(r.read_u8(), Ok(*x)),
+        Op::I8(x) => prop_assert_eq!(r.read_i8(), Ok(*x)),
+        Op::U16(x) => prop_assert_eq!(r.read_u16(), Ok(*x)),
+        Op::U32(x) => prop_assert_eq!(r.read_u32(), Ok(*x)),
+        Op::U64(x) => prop_assert_eq!(r.read_u64(), Ok(*x)),
+        Op::I16(x) => prop_assert_eq!(r.read_i16(), Ok(*x)),
+        Op::I32(x) => prop_assert_eq!(r.read_i32(), Ok(*x)),
+        Op::I64(x) => prop_assert_eq!(r.read_i64(), Ok(*x)),
+        Op::F32(x) => prop_assert_eq!(r.read_f32().map(f32::to_bits), Ok(x.to_bits())),
+        Op::F64(x) => prop_assert_eq!(r.read_f64().map(f64::to_bits), Ok(x.to_bits())),
+        Op::Bool(x) => prop_assert_eq!(r.read_bool(), Ok(*x)),
+        Op::Char(x) => prop_assert_eq!(r.read_char(), Ok(*x)),
+        Op::Int7I32(x) => prop_assert_eq!(r.read_7bit_encoded_i32_strict(), Ok(*x)),
+        Op::Int7I64(x) => prop_assert_eq!(r.read_7bit_encoded_i64_strict(), Ok(*x)),
+        Op::Utf8(s) => prop_assert_eq!(r.read_utf8_str(), Ok(s.as_str())),
+        Op::Utf16(s) => {
+            let wchars = read_wchars(r)?;
+            let decoded = String::from_utf16(&wchars).ok();
+            prop_assert_eq!(decoded.as_ref(), Some(s));
+        }
+        Op::Utf16Wchars(s) => prop_assert_eq!(&read_wchars(r)?, s),
+        Op::Bytes(b) => prop_assert_eq!(r.read_length_prefixed_bytes(), Ok(b.as_slice())),
+        Op::Decimal(bits) => prop_assert_eq!(r.read_decimal_bits(), Ok(*bits)),
+    }
+    Ok(())
+}
+
+/// Reads a length-prefixed UTF-16 string and copies its code units.
+fn read_wchars(r: &mut BinaryReader<'_>) -> Result<Vec<u16>, TestCaseError> {
+    let wchars = r
+        .read_utf16_wchars()
+        .map_err(|e| TestCaseError::fail(e.to_string()))?;
+    Ok(wchars.iter().map(|c| c.get()).collect())
+}
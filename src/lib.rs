@@ -14,5 +14,7 @@ mod writer;
 #[cfg(test)]
 mod tests;
 
-pub use reader::{BinaryReader, BinaryReaderError};
+#[cfg(feature = "std")]
+pub use reader::CursorReader;
+pub use reader::{BinaryReader, BinaryReaderError, Bytes, VarIntDecoder};
 pub use writer::{BinaryWriter, BinaryWriterError};
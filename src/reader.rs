@@ -1,8 +1,14 @@
-#[cfg(feature = "std")]
-use std::borrow::Cow;
+extern crate alloc;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 
-use zerocopy::byteorder::{LE, U16};
-use zerocopy::FromBytes;
+use crate::writer::TICKS_PER_SECOND;
+#[cfg(feature = "std")]
+use crate::writer::{MAX_DATETIME_TICKS, UNIX_EPOCH_TICKS};
+use crate::BinaryWriter;
+use zerocopy::byteorder::{BE, F32, F64, LE, U16};
+use zerocopy::{FromBytes, Immutable};
 
 pub type Result<T> = core::result::Result<T, BinaryReaderError>;
 
@@ -21,22 +27,173 @@ pub type Result<T> = core::result::Result<T, BinaryReaderError>;
 /// Another option is to use "restartable" decoding.  Before calling any function that decodes a
 /// value, read the `data` slice (or simply its length). Then, call a function to decode a value
 /// (potentially multiple calls to decode multiple values).  If any function fails with
-/// `Err(ReaderError::NeedsMoreData)`, then go read more data from the source and reset `data`
-/// to point to the original location, plus any new data. Then repeat the calls that decode data.
+/// `Err(BinaryReaderError::NeedsMoreData { .. })`, then go read more data from the source and
+/// reset `data` to point to the original location, plus any new data. Then repeat the calls that
+/// decode data.
 ///
 /// This is feasible and it may be necessary for some designs. However, simply reading data into
 /// `Vec<u8>` or another in-memory container is likely to be simpler, less bug-prone, and
 /// probably faster, too.
+#[derive(Clone)]
 pub struct BinaryReader<'a> {
     /// The input data being parsed. Each time a value is parsed from `data`, `data` is reassigned
     /// to the remaining data.
     pub data: &'a [u8],
+
+    /// The buffer that the reader was created over. `data` is always a suffix of `start`, unless
+    /// the application reassigns `data`. This is used for computing the read position.
+    start: &'a [u8],
+
+    /// The position within `start` at which reading began. This is nonzero for readers created
+    /// by `new_at` and `from_cursor`.
+    start_pos: usize,
+
+    /// The maximum element count that `read_vec` will accept.
+    max_count: usize,
 }
 
+/// The maximum number of elements that `read_vec` will preallocate space for. The count is read
+/// from the input, so it cannot be trusted to size an allocation.
+const MAX_PREALLOC_COUNT: usize = 4096;
+
 impl<'a> BinaryReader<'a> {
     /// Constructor
     pub fn new(data: &'a [u8]) -> Self {
-        Self { data }
+        Self {
+            data,
+            start: data,
+            start_pos: 0,
+            max_count: usize::MAX,
+        }
+    }
+
+    /// Creates a reader over `data` that starts reading at offset `pos`. [`Self::position`]
+    /// reports offsets relative to the start of `data`, so the first value read is at position
+    /// `pos`.
+    ///
+    /// This is useful for formats that locate their contents using a table of offsets. If `pos`
+    /// is greater than the length of `data`, then this returns
+    /// `Err(BinaryReaderError::NeedsMoreData { .. })`.
+    pub fn new_at(data: &'a [u8], pos: usize) -> Result<Self> {
+        let Some(rest) = data.get(pos..) else {
+            return Err(BinaryReaderError::NeedsMoreData {
+                needed: pos - data.len(),
+            });
+        };
+
+        Ok(Self {
+            data: rest,
+            start: data,
+            start_pos: pos,
+            max_count: usize::MAX,
+        })
+    }
+
+    /// Creates a reader over the unread portion of `cursor`, i.e. the bytes starting at the
+    /// cursor's current position.
+    ///
+    /// The returned [`CursorReader`] dereferences to a `BinaryReader`. The reader's
+    /// [`Self::position`] is relative to the start of the cursor's buffer. The reader borrows
+    /// the cursor, so the cursor cannot be modified while the reader is in use. When reading is
+    /// done, [`CursorReader::commit`] returns the position just past the data that was
+    /// consumed; pass it to `cursor.set_position` to advance the cursor. If the position is not
+    /// committed, the cursor is left where it was.
+    ///
+    /// If the cursor is positioned past the end of its buffer, then this returns
+    /// `Err(BinaryReaderError::NeedsMoreData { .. })`, as [`Self::new_at`] does.
+    #[cfg(feature = "std")]
+    pub fn from_cursor<T: AsRef<[u8]>>(cursor: &'a std::io::Cursor<T>) -> Result<CursorReader<'a>> {
+        let buffer = cursor.get_ref().as_ref();
+        let pos = usize::try_from(cursor.position()).unwrap_or(usize::MAX);
+        let reader = Self::new_at(buffer, pos)?;
+        Ok(CursorReader { reader })
+    }
+
+    /// Sets the maximum element count that [`Self::read_vec`] will accept. If a count is larger
+    /// than this, then `read_vec` returns `Err(BinaryReaderError::Invalid)`. By default, there is
+    /// no limit.
+    ///
+    /// The other functions that copy a length-prefixed or counted sequence into a new
+    /// allocation, such as [`Self::read_f32_slice`] and [`Self::read_boxed_bytes`], apply the
+    /// same limit to their element count (for byte buffers, the number of bytes).
+    pub fn set_max_count(&mut self, max_count: usize) {
+        self.max_count = max_count;
+    }
+
+    /// Resets the reader so that it reads from `data`. This allows a single `BinaryReader` to be
+    /// reused for many independent buffers. The read position is reset to zero.
+    pub fn reset(&mut self, data: &'a [u8]) {
+        self.data = data;
+        self.start = data;
+        self.start_pos = 0;
+    }
+
+    /// Returns the number of bytes that have been consumed since the reader was created (or
+    /// reset).
+    ///
+    /// If the application reassigns `data` to point to a different buffer, then the position is
+    /// not meaningful.
+    pub fn position(&self) -> usize {
+        self.start.len().saturating_sub(self.data.len())
+    }
+
+    /// Reads a `u32` in little-endian byte order at `offset`, relative to the start of the buffer
+    /// that the reader was created over (or reset to). This does not change the read position.
+    ///
+    /// This is useful for formats that contain a table of offsets. If the value extends past the
+    /// end of the buffer, then this returns `Err(BinaryReaderError::NeedsMoreData { .. })`.
+    pub fn peek_u32_at(&self, offset: usize) -> Result<u32> {
+        let Some(end) = offset.checked_add(4) else {
+            return Err(BinaryReaderError::Invalid);
+        };
+
+        let Some(bytes) = self.start.get(offset..end) else {
+            return Err(BinaryReaderError::NeedsMoreData {
+                needed: end - self.start.len(),
+            });
+        };
+
+        // This unwrap() call will get optimized out.
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Returns the bytes that have been consumed since `start` was saved from `data`.
+    ///
+    /// This is useful when an application needs both the parsed values and the raw bytes that
+    /// encode them, such as for verifying a signature. Save `data` before parsing, then call this
+    /// afterward.
+    ///
+    /// Returns `None` if the current `data` does not begin within `start` (for example, if the
+    /// application reassigned `data` to a different buffer).
+    pub fn consumed_since(&self, start: &'a [u8]) -> Option<&'a [u8]> {
+        let offset = (self.data.as_ptr() as usize).checked_sub(start.as_ptr() as usize)?;
+        start.get(..offset)
+    }
+
+    /// Checks that all of the input data has been consumed.
+    ///
+    /// Call this after parsing a complete message. If there is any data remaining, then this
+    /// returns `Err(BinaryReaderError::TrailingData)`.
+    pub fn finish(&self) -> Result<()> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(BinaryReaderError::TrailingData)
+        }
+    }
+
+    /// Checks that all of the input data has been consumed, and returns the number of bytes that
+    /// were consumed since the reader was created (or reset).
+    ///
+    /// This is like [`Self::finish`], but the returned length is useful when messages are
+    /// stored one after another, since it tells the caller where the next message begins. If
+    /// there is any data remaining, then this returns `Err(BinaryReaderError::TrailingData)`.
+    ///
+    /// For a reader created by [`Self::new_at`] (or `from_cursor`), this counts from the
+    /// starting position, so it is the final [`Self::position`] minus the starting position.
+    pub fn assert_consumed(&self) -> Result<usize> {
+        self.finish()?;
+        Ok(self.position().saturating_sub(self.start_pos))
     }
 
     /// Reads a single `u8` value.
@@ -47,10 +204,16 @@ impl<'a> BinaryReader<'a> {
             self.data = &self.data[1..];
             Ok(value)
         } else {
-            Err(BinaryReaderError::NeedsMoreData)
+            Err(BinaryReaderError::NeedsMoreData { needed: 1 })
         }
     }
 
+    /// Reads a single `i8` value.
+    #[inline(always)]
+    pub fn read_i8(&mut self) -> Result<i8> {
+        Ok(self.read_u8()? as i8)
+    }
+
     /// Reads a single `bool` value.
     #[inline(always)]
     pub fn read_bool(&mut self) -> Result<bool> {
@@ -59,16 +222,103 @@ impl<'a> BinaryReader<'a> {
             self.data = &self.data[1..];
             Ok(value != 0)
         } else {
-            Err(BinaryReaderError::NeedsMoreData)
+            Err(BinaryReaderError::NeedsMoreData { needed: 1 })
         }
     }
 
+    /// Reads a `char` value, encoded as UTF-8. This matches the behavior of .NET's
+    /// `BinaryReader.ReadChar` when the reader uses its default encoding (UTF-8).
+    ///
+    /// This reads 1 to 4 bytes, depending on the first byte. If the bytes are not a well-formed
+    /// UTF-8 sequence, then this returns `Err(BinaryReaderError::Invalid)`.
+    pub fn read_char(&mut self) -> Result<char> {
+        let Some(&b0) = self.data.first() else {
+            return Err(BinaryReaderError::NeedsMoreData { needed: 1 });
+        };
+
+        let len = match b0 {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Err(BinaryReaderError::Invalid),
+        };
+
+        let Some(bytes) = self.data.get(..len) else {
+            return Err(BinaryReaderError::NeedsMoreData {
+                needed: len - self.data.len(),
+            });
+        };
+
+        let Ok(s) = core::str::from_utf8(bytes) else {
+            return Err(BinaryReaderError::Invalid);
+        };
+
+        // This unwrap() cannot fail; `s` is not empty.
+        let c = s.chars().next().unwrap();
+        self.data = &self.data[len..];
+        Ok(c)
+    }
+
+    /// Reads a `char` value, encoded as UTF-16 in little-endian byte order. This matches the
+    /// behavior of .NET's `BinaryReader.ReadChar` when the reader uses `Encoding.Unicode`.
+    ///
+    /// This reads one code unit (2 bytes), or two code units (4 bytes) if the first code unit is
+    /// a high surrogate. If the input contains a lone surrogate, then this returns
+    /// `Err(BinaryReaderError::Invalid)`. The reader is not advanced if this function fails.
+    pub fn read_char_utf16(&mut self) -> Result<char> {
+        let mut r = BinaryReader::new(self.data);
+        let c0 = r.read_u16()?;
+        let c = if (0xd800..0xdc00).contains(&c0) {
+            let c1 = r.read_u16()?;
+            char::decode_utf16([c0, c1]).next()
+        } else {
+            char::decode_utf16([c0]).next()
+        };
+
+        let Some(Ok(c)) = c else {
+            return Err(BinaryReaderError::Invalid);
+        };
+
+        self.data = r.data;
+        Ok(c)
+    }
+
+    /// Reads a single ASCII character, encoded as one byte.
+    ///
+    /// If the byte is not in the ASCII range (0x00 to 0x7F), then this returns
+    /// `Err(BinaryReaderError::Invalid)` and the reader is not advanced.
+    #[inline(always)]
+    pub fn read_ascii_char(&mut self) -> Result<char> {
+        match self.data.first() {
+            Some(&b) if b.is_ascii() => {
+                self.data = &self.data[1..];
+                Ok(b as char)
+            }
+            Some(_) => Err(BinaryReaderError::Invalid),
+            None => Err(BinaryReaderError::NeedsMoreData { needed: 1 }),
+        }
+    }
+
+    /// Reads a 4-byte `bool` value, such as a Win32 `BOOL`. The value is read as a `u32` in
+    /// little-endian byte order. Any non-zero value is treated as `true`.
+    #[inline(always)]
+    pub fn read_bool32(&mut self) -> Result<bool> {
+        Ok(self.read_u32()? != 0)
+    }
+
     /// Reads a slice of bytes whose length is `len`. This function returns a slice reference
     /// to the bytes; it does not copy them.
+    ///
+    /// If fewer than `len` bytes are available, then this returns
+    /// `Err(BinaryReaderError::NeedsMoreData { needed })`, where `needed` is exactly the number of
+    /// missing bytes, and the reader is not advanced.
     #[inline(always)]
     pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
         if self.data.len() < len {
-            Err(BinaryReaderError::NeedsMoreData)
+            Err(BinaryReaderError::NeedsMoreData {
+                needed: len - self.data.len(),
+            })
         } else {
             let (lo, hi) = self.data.split_at(len);
             self.data = hi;
@@ -76,11 +326,187 @@ impl<'a> BinaryReader<'a> {
         }
     }
 
+    /// Reads a slice of bytes whose length is `len`, where `len` must not exceed `max`.
+    ///
+    /// This is for lengths that come from untrusted input. If `len` is greater than `max`, then
+    /// this returns `Err(BinaryReaderError::Invalid)`, even if the input contains enough data.
+    /// Otherwise, this is the same as [`Self::read_bytes`].
+    pub fn read_bytes_max(&mut self, len: usize, max: usize) -> Result<&'a [u8]> {
+        if len > max {
+            return Err(BinaryReaderError::Invalid);
+        }
+        self.read_bytes(len)
+    }
+
+    /// Reads a slice of bytes whose length is `len`, passes it to `f`, and returns it.
+    ///
+    /// This is useful for updating a checksum or hash over the data as it is read, without
+    /// iterating over it a second time. `f` is only called if the bytes were read successfully.
+    pub fn read_bytes_with<F: FnOnce(&[u8])>(&mut self, len: usize, f: F) -> Result<&'a [u8]> {
+        let bytes = self.read_bytes(len)?;
+        f(bytes);
+        Ok(bytes)
+    }
+
+    /// Reads `expected.len()` bytes and checks that they are equal to `expected`. This is useful
+    /// for checking magic numbers and signatures.
+    ///
+    /// If the bytes do not match, then this returns `Err(BinaryReaderError::Invalid)`. The
+    /// reader is only advanced if the bytes match.
+    pub fn expect_bytes(&mut self, expected: &[u8]) -> Result<()> {
+        let Some(actual) = self.data.get(..expected.len()) else {
+            return Err(BinaryReaderError::NeedsMoreData {
+                needed: expected.len() - self.data.len(),
+            });
+        };
+
+        if actual != expected {
+            return Err(BinaryReaderError::Invalid);
+        }
+
+        self.data = &self.data[expected.len()..];
+        Ok(())
+    }
+
+    /// Reads a sequence of bytes whose length is `len` and copies it into a new `Box<[u8]>`.
+    ///
+    /// The returned buffer has exactly `len` bytes, with no spare capacity. The length is
+    /// validated against the input data before any memory is allocated. If `len` is larger than
+    /// the limit set by [`Self::set_max_count`], then this returns
+    /// `Err(BinaryReaderError::Invalid)`.
+    pub fn read_boxed_bytes(&mut self, len: usize) -> Result<Box<[u8]>> {
+        Ok(Box::from(self.read_bytes_max(len, self.max_count)?))
+    }
+
+    /// Skips padding bytes until the read position is a multiple of `align`.
+    ///
+    /// The reader does not know where `data` is located within the encoded stream, so the caller
+    /// provides `base`, which is the offset of the current read position (the start of `data`)
+    /// within the stream. The contents of the padding bytes are not checked.
+    ///
+    /// If `align` is zero, then this returns `Err(BinaryReaderError::Invalid)`.
+    pub fn skip_align_to(&mut self, align: usize, base: usize) -> Result<()> {
+        if align == 0 {
+            return Err(BinaryReaderError::Invalid);
+        }
+        let rem = base % align;
+        if rem != 0 {
+            self.read_bytes(align - rem)?;
+        }
+        Ok(())
+    }
+
+    /// Skips padding bytes until [`Self::position`] is a multiple of `align`, and checks that
+    /// the padding bytes are all zero.
+    ///
+    /// Unlike [`Self::skip_align_to`], this uses the reader's own position, so alignment is
+    /// relative to the start of the buffer that the reader was created over. If any padding
+    /// byte is non-zero, then this returns `Err(BinaryReaderError::Invalid)`. The reader is not
+    /// advanced if this function fails.
+    ///
+    /// If `align` is zero, then this returns `Err(BinaryReaderError::Invalid)`.
+    pub fn skip_padding_to_multiple(&mut self, align: usize) -> Result<()> {
+        if align == 0 {
+            return Err(BinaryReaderError::Invalid);
+        }
+        let rem = self.position() % align;
+        if rem == 0 {
+            return Ok(());
+        }
+
+        let len = align - rem;
+        let Some(padding) = self.data.get(..len) else {
+            return Err(BinaryReaderError::NeedsMoreData {
+                needed: len - self.data.len(),
+            });
+        };
+
+        if padding.iter().any(|&b| b != 0) {
+            return Err(BinaryReaderError::Invalid);
+        }
+
+        self.data = &self.data[len..];
+        Ok(())
+    }
+
+    /// Reads `len` bytes and returns a new `BinaryReader` that reads from only those bytes.
+    ///
+    /// This is useful for parsing a nested message whose length is known. Reading past the end
+    /// of the returned reader fails with `Err(BinaryReaderError::NeedsMoreData { .. })`, rather
+    /// than reading data that follows the nested message. The new reader has the same settings
+    /// (such as [`Self::set_max_count`]) as this reader, and its position starts at zero.
+    pub fn read_subreader(&mut self, len: usize) -> Result<BinaryReader<'a>> {
+        let data = self.read_bytes(len)?;
+        Ok(BinaryReader {
+            data,
+            start: data,
+            start_pos: 0,
+            max_count: self.max_count,
+        })
+    }
+
+    /// Reads a length-prefixed block, such as one written by `BinaryWriter::write_subframe`, and
+    /// calls `f` to parse its contents. The length is encoded as a 7-bit encoded `i32`.
+    ///
+    /// `f` is given a reader over only the contents of the block (see [`Self::read_subreader`]).
+    /// After `f` returns, this checks that `f` consumed all of the block. If it did not, then
+    /// this returns `Err(BinaryReaderError::TrailingData)`. This catches disagreements between
+    /// the length of the block and the lengths of the fields inside it.
+    pub fn read_exact_subframe<U, F>(&mut self, f: F) -> Result<U>
+    where
+        F: FnOnce(&mut BinaryReader<'a>) -> Result<U>,
+    {
+        let len = self.read_7bit_encoded_len()?;
+        let mut inner = self.read_subreader(len)?;
+        let value = f(&mut inner)?;
+        inner.finish()?;
+        Ok(value)
+    }
+
+    /// Returns a new reader over the remaining data, without modifying this reader.
+    ///
+    /// This is useful for scanning ahead, such as for diagnostics or speculative parsing, while
+    /// leaving this reader where it is. The new reader has the same settings as this reader, and
+    /// its position starts at zero. To keep the same position, use `clone()` instead.
+    pub fn clone_remaining(&self) -> BinaryReader<'a> {
+        BinaryReader {
+            data: self.data,
+            start: self.data,
+            start_pos: 0,
+            max_count: self.max_count,
+        }
+    }
+
+    /// Splits the remaining data at `len` and returns two readers: one over the first `len`
+    /// bytes and one over the rest. This reader is not modified.
+    ///
+    /// This is useful for formats where sections are located by an offset table and are
+    /// processed independently. Both readers have the same settings as this reader, and their
+    /// positions start at zero.
+    pub fn split_at(&self, len: usize) -> Result<(BinaryReader<'a>, BinaryReader<'a>)> {
+        if self.data.len() < len {
+            return Err(BinaryReaderError::NeedsMoreData {
+                needed: len - self.data.len(),
+            });
+        }
+
+        let (lo, hi) = self.data.split_at(len);
+        let reader = |data| BinaryReader {
+            data,
+            start: data,
+            start_pos: 0,
+            max_count: self.max_count,
+        };
+        Ok((reader(lo), reader(hi)))
+    }
+
     /// Reads a small array of bytes, with a constant length.
     #[inline(always)]
     pub fn read_cbytes<const N: usize>(&mut self) -> Result<[u8; N]> {
         if self.data.len() < N {
-            Err(BinaryReaderError::NeedsMoreData)
+            Err(BinaryReaderError::NeedsMoreData {
+                needed: N - self.data.len(),
+            })
         } else {
             let (lo, hi) = self.data.split_at(N);
             self.data = hi;
@@ -93,7 +519,9 @@ impl<'a> BinaryReader<'a> {
     #[inline(always)]
     pub fn read_cbytes_ref<const N: usize>(&mut self) -> Result<&[u8; N]> {
         if self.data.len() < N {
-            Err(BinaryReaderError::NeedsMoreData)
+            Err(BinaryReaderError::NeedsMoreData {
+                needed: N - self.data.len(),
+            })
         } else {
             let (lo, hi) = self.data.split_at(N);
             self.data = hi;
@@ -102,12 +530,110 @@ impl<'a> BinaryReader<'a> {
         }
     }
 
+    /// Reads `count` values of type `T` and returns them as a slice. This function does not copy
+    /// the data.
+    ///
+    /// This is useful for reading arrays of fixed-size types, such as `U32<LE>` or structs that
+    /// implement `FromBytes`. If the input is too short, then this returns
+    /// `Err(BinaryReaderError::NeedsMoreData { .. })`. If the data is not suitably aligned for
+    /// `T`, then this returns `Err(BinaryReaderError::Invalid)`. Types from `zerocopy::byteorder`
+    /// have an alignment of 1, so they never fail due to alignment.
+    pub fn read_slice<T: FromBytes + Immutable>(&mut self, count: usize) -> Result<&'a [T]> {
+        let Some(len) = count.checked_mul(core::mem::size_of::<T>()) else {
+            return Err(BinaryReaderError::Invalid);
+        };
+
+        if self.data.len() < len {
+            return Err(BinaryReaderError::NeedsMoreData {
+                needed: len - self.data.len(),
+            });
+        }
+
+        let Ok(values) = <[T]>::ref_from_prefix_with_elems(self.data, count) else {
+            return Err(BinaryReaderError::Invalid);
+        };
+
+        self.data = &self.data[len..];
+        Ok(values.0)
+    }
+
+    /// Reads `count` values of type `T`. If the data is suitably aligned for `T`, then this
+    /// returns a borrowed slice, without copying. Otherwise, the values are copied into a new,
+    /// correctly aligned `Vec<T>`.
+    ///
+    /// This is like [`Self::read_slice`], except that it does not fail due to alignment.
+    pub fn read_slice_cow<T>(&mut self, count: usize) -> Result<Cow<'a, [T]>>
+    where
+        T: FromBytes + Immutable + Clone,
+    {
+        let size = core::mem::size_of::<T>();
+        let Some(len) = count.checked_mul(size) else {
+            return Err(BinaryReaderError::Invalid);
+        };
+
+        let bytes = self.read_bytes(len)?;
+
+        if let Ok(values) = <[T]>::ref_from_bytes_with_elems(bytes, count) {
+            return Ok(Cow::Borrowed(values));
+        }
+
+        let mut values = Vec::with_capacity(count);
+        for i in 0..count {
+            // This cannot fail; the sizes are correct.
+            let Ok(value) = T::read_from_bytes(&bytes[i * size..(i + 1) * size]) else {
+                return Err(BinaryReaderError::Invalid);
+            };
+            values.push(value);
+        }
+        Ok(Cow::Owned(values))
+    }
+
+    /// Reads `count` `f32` values, each in little-endian byte order, and copies them into a new
+    /// `Vec<f32>`.
+    ///
+    /// If `count` is larger than the limit set by [`Self::set_max_count`], then this returns
+    /// `Err(BinaryReaderError::Invalid)`. The length is validated against the input data before
+    /// any memory is allocated.
+    pub fn read_f32_slice(&mut self, count: usize) -> Result<Vec<f32>> {
+        if count > self.max_count {
+            return Err(BinaryReaderError::Invalid);
+        }
+        let values = self.read_slice::<F32<LE>>(count)?;
+        Ok(values.iter().map(|v| v.get()).collect())
+    }
+
+    /// Reads `count` `f64` values, each in little-endian byte order, and copies them into a new
+    /// `Vec<f64>`. See [`Self::read_f32_slice`].
+    pub fn read_f64_slice(&mut self, count: usize) -> Result<Vec<f64>> {
+        if count > self.max_count {
+            return Err(BinaryReaderError::Invalid);
+        }
+        let values = self.read_slice::<F64<LE>>(count)?;
+        Ok(values.iter().map(|v| v.get()).collect())
+    }
+
     /// Reads a `u16` in little-endian byte order.
     #[inline(always)]
     pub fn read_u16(&mut self) -> Result<u16> {
         Ok(u16::from_le_bytes(self.read_cbytes()?))
     }
 
+    /// Reads a 24-bit unsigned integer (3 bytes) in little-endian byte order. The value is
+    /// returned in the low 24 bits of a `u32`.
+    #[inline(always)]
+    pub fn read_u24(&mut self) -> Result<u32> {
+        let [b0, b1, b2] = self.read_cbytes()?;
+        Ok(u32::from_le_bytes([b0, b1, b2, 0]))
+    }
+
+    /// Reads a 24-bit unsigned integer (3 bytes) in big-endian byte order. The value is returned
+    /// in the low 24 bits of a `u32`.
+    #[inline(always)]
+    pub fn read_u24_be(&mut self) -> Result<u32> {
+        let [b0, b1, b2] = self.read_cbytes()?;
+        Ok(u32::from_be_bytes([0, b0, b1, b2]))
+    }
+
     /// Reads a `u32` in little-endian byte order.
     #[inline(always)]
     pub fn read_u32(&mut self) -> Result<u32> {
@@ -138,58 +664,328 @@ impl<'a> BinaryReader<'a> {
         Ok(i64::from_le_bytes(self.read_cbytes()?))
     }
 
+    /// Reads a set of flags, stored as a `u32` in little-endian byte order, and checks that no
+    /// bits are set other than those in `known`.
+    ///
+    /// .NET `[Flags]` enums are serialized as their underlying integer. If any bit that is not
+    /// in `known` is set, then this returns `Err(BinaryReaderError::Invalid)` and the reader is
+    /// not advanced. To accept unknown bits, use [`Self::read_u32`] instead.
+    ///
+    /// This pairs well with the `bitflags` crate, e.g.
+    /// `Flags::from_bits_retain(r.read_flags_u32(Flags::all().bits())?)`. Flags are written with
+    /// `BinaryWriter::write_u32(flags.bits())`.
+    pub fn read_flags_u32(&mut self, known: u32) -> Result<u32> {
+        let Some(&bytes) = self.data.first_chunk::<4>() else {
+            return Err(BinaryReaderError::NeedsMoreData {
+                needed: 4 - self.data.len(),
+            });
+        };
+
+        let value = u32::from_le_bytes(bytes);
+        if value & !known != 0 {
+            return Err(BinaryReaderError::Invalid);
+        }
+
+        self.data = &self.data[4..];
+        Ok(value)
+    }
+
+    /// Reads an `f32` in little-endian byte order.
+    #[inline(always)]
+    pub fn read_f32(&mut self) -> Result<f32> {
+        Ok(f32::from_le_bytes(self.read_cbytes()?))
+    }
+
+    /// Reads an `f64` in little-endian byte order.
+    #[inline(always)]
+    pub fn read_f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.read_cbytes()?))
+    }
+
+    /// Reads a `half::f16` (half-precision float) in little-endian byte order. This matches .NET's
+    /// `BinaryReader.ReadHalf`.
+    #[cfg(feature = "half")]
+    #[inline(always)]
+    pub fn read_f16(&mut self) -> Result<half::f16> {
+        Ok(half::f16::from_le_bytes(self.read_cbytes()?))
+    }
+
+    /// Reads an `f32` in big-endian byte order.
+    ///
+    /// .NET's `BinaryReader` always uses little-endian byte order. This is provided for formats
+    /// that mix .NET-encoded data with big-endian data.
+    #[inline(always)]
+    pub fn read_f32_be(&mut self) -> Result<f32> {
+        Ok(f32::from_be_bytes(self.read_cbytes()?))
+    }
+
+    /// Reads an `f64` in big-endian byte order.
+    ///
+    /// .NET's `BinaryReader` always uses little-endian byte order. This is provided for formats
+    /// that mix .NET-encoded data with big-endian data.
+    #[inline(always)]
+    pub fn read_f64_be(&mut self) -> Result<f64> {
+        Ok(f64::from_be_bytes(self.read_cbytes()?))
+    }
+
     /// Reads a variable-length integer and returns the value in `i32`.
+    ///
+    /// If the input ends before the last byte of the integer, this returns
+    /// `BinaryReaderError::NeedsMoreData { .. }` and does not advance `data`, so the caller
+    /// can append more data and call this again.
     pub fn read_7bit_encoded_i32(&mut self) -> Result<i32> {
         // Each byte encodes 7 bits of the integer and 1 bit indicating whether there are
         // more bytes following this one. Because 32 is not evenly divisible by 7, the last
         // byte has some meaningless bits in them. We could validate those bits (rejecting
         // input where the last byte contains non-zero meaningless bits), but that would be
         // stricter than the .NET implementation, so we do not.
+        let mut decoder = VarIntDecoder::new_i32();
+        self.read_with_decoder(&mut decoder)?;
+        Ok(decoder.value_i32())
+    }
 
-        const MORE: u8 = 0x80;
+    /// Reads a variable-length integer and returns the value in `i32`, along with the number of
+    /// bytes that encoded it. Otherwise, this is the same as [`Self::read_7bit_encoded_i32`].
+    pub fn read_7bit_encoded_i32_counted(&mut self) -> Result<(i32, usize)> {
+        let (value, len) = self.peek_7bit_encoded_i32()?;
+        self.data = &self.data[len..];
+        Ok((value, len))
+    }
 
-        let mut shift: u32 = 0;
-        let mut n: u32 = 0;
+    /// Reads a variable-length integer and checks that its value is in the range `min..=max`.
+    ///
+    /// This is useful for values such as enum discriminants and indexes, which have a known
+    /// range. If the value is outside of the range, then this returns
+    /// `Err(BinaryReaderError::Invalid)` and the reader is not advanced.
+    pub fn read_7bit_encoded_i32_ranged(&mut self, min: i32, max: i32) -> Result<i32> {
+        self.try_read(|r| {
+            let value = r.read_7bit_encoded_i32()?;
+            if (min..=max).contains(&value) {
+                Ok(value)
+            } else {
+                Err(BinaryReaderError::Invalid)
+            }
+        })
+    }
 
-        loop {
-            let b = self.read_u8()?;
-            n |= ((b & 0x7f) as u32) << shift;
+    /// Reads a variable-length integer and returns the value in `i64`.
+    ///
+    /// Like [`Self::read_7bit_encoded_i32`], this does not advance `data` if the input ends
+    /// before the last byte of the integer.
+    pub fn read_7bit_encoded_i64(&mut self) -> Result<i64> {
+        let mut decoder = VarIntDecoder::new_i64();
+        self.read_with_decoder(&mut decoder)?;
+        Ok(decoder.value_i64())
+    }
 
-            if (b & MORE) == 0 {
-                break;
-            }
+    /// Reads the discriminant (tag) of a tagged union, which was written by
+    /// `BinaryWriter::write_tag`. The tag is a 7-bit encoded integer.
+    pub fn read_tag(&mut self) -> Result<u32> {
+        Ok(self.read_7bit_encoded_i32()? as u32)
+    }
 
-            shift += 7;
-            if shift >= 32 {
-                return Err(BinaryReaderError::Invalid);
+    /// Decodes a variable-length `i32` without advancing `data`. Returns the value and the
+    /// number of bytes that encode it.
+    ///
+    /// This is useful for inspecting an upcoming length prefix. The caller can advance past the
+    /// integer by calling [`Self::read_bytes`] with the returned length.
+    pub fn peek_7bit_encoded_i32(&self) -> Result<(i32, usize)> {
+        let mut decoder = VarIntDecoder::new_i32();
+        let len = self.peek_with_decoder(&mut decoder)?;
+        Ok((decoder.value_i32(), len))
+    }
+
+    /// Feeds bytes from `data` to `decoder` until it completes. `data` is only advanced if the
+    /// decoder completes successfully.
+    fn read_with_decoder(&mut self, decoder: &mut VarIntDecoder) -> Result<()> {
+        let len = self.peek_with_decoder(decoder)?;
+        self.data = &self.data[len..];
+        Ok(())
+    }
+
+    /// Feeds bytes from `data` to `decoder` until it completes, and returns the number of bytes
+    /// that were used.
+    fn peek_with_decoder(&self, decoder: &mut VarIntDecoder) -> Result<usize> {
+        for (i, &b) in self.data.iter().enumerate() {
+            if decoder.push(b)? {
+                return Ok(i + 1);
             }
         }
+        Err(BinaryReaderError::NeedsMoreData { needed: 1 })
+    }
+
+    /// Reads a variable-length integer that specifies a length, such as the length prefix of a
+    /// string, and returns it as `usize`.
+    ///
+    /// .NET encodes lengths as a 7-bit encoded `i32`. If the decoded value is negative, then this
+    /// returns `Err(BinaryReaderError::Invalid)`.
+    pub fn read_7bit_encoded_len(&mut self) -> Result<usize> {
+        let len_i32 = self.read_7bit_encoded_i32()?;
+        usize::try_from(len_i32).map_err(|_| BinaryReaderError::Invalid)
+    }
 
-        Ok(n as i32)
+    /// Advances past a variable-length integer without decoding it.
+    ///
+    /// This consumes bytes until it finds a byte that does not have the "more" bit set. The
+    /// encoding may be at most 10 bytes long, which is the longest encoding of an `i64`. If the
+    /// encoding is longer than that, then this returns `Err(BinaryReaderError::Invalid)`.
+    ///
+    /// Like [`Self::read_7bit_encoded_i64`], this does not advance `data` if it fails.
+    pub fn skip_7bit_encoded(&mut self) -> Result<()> {
+        self.read_with_decoder(&mut VarIntDecoder::new_i64())
     }
 
-    /// Reads a variable-length integer and returns the value in `i64`.
-    pub fn read_7bit_encoded_i64(&mut self) -> Result<i64> {
-        const MORE: u8 = 0x80;
+    /// Reads a variable-length integer and returns the value in `i32`, using the same validation
+    /// rules as .NET's `BinaryReader.Read7BitEncodedInt`.
+    ///
+    /// This is stricter than [`Self::read_7bit_encoded_i32`]. The 5th byte of the encoding can
+    /// only contain 4 meaningful bits. If any of the other bits in the 5th byte are set, then
+    /// this function returns `Err(BinaryReaderError::Invalid)`. The reader is not advanced if
+    /// this function fails.
+    pub fn read_7bit_encoded_i32_strict(&mut self) -> Result<i32> {
+        let mut decoder = VarIntDecoder::new_i32_strict();
+        self.read_with_decoder(&mut decoder)?;
+        Ok(decoder.value_i32())
+    }
 
-        let mut shift: u32 = 0;
-        let mut n: u64 = 0;
+    /// Reads a variable-length integer and returns the value in `i64`, using the same validation
+    /// rules as .NET's `BinaryReader.Read7BitEncodedInt64`.
+    ///
+    /// This is stricter than [`Self::read_7bit_encoded_i64`]. The 10th byte of the encoding can
+    /// only contain 1 meaningful bit. If any of the other bits in the 10th byte are set, then
+    /// this function returns `Err(BinaryReaderError::Invalid)`. The reader is not advanced if
+    /// this function fails.
+    pub fn read_7bit_encoded_i64_strict(&mut self) -> Result<i64> {
+        let mut decoder = VarIntDecoder::new_i64_strict();
+        self.read_with_decoder(&mut decoder)?;
+        Ok(decoder.value_i64())
+    }
+
+    /// Reads a variable-length integer and returns the value in `i32`. The encoding must be
+    /// canonical, meaning that it must be identical to the output of
+    /// [`BinaryWriter::write_7bit_encoded_i32`] for the decoded value.
+    ///
+    /// This is stricter than [`Self::read_7bit_encoded_i32_strict`]. In addition to the checks
+    /// that it performs, this rejects "overlong" encodings that use more bytes than necessary,
+    /// such as `[0x80, 0x00]` for the value 0. If the encoding is not canonical, then this
+    /// returns `Err(BinaryReaderError::Invalid)` and the reader is not advanced.
+    pub fn read_7bit_encoded_i32_checked(&mut self) -> Result<i32> {
+        let saved = self.data;
+        let value = self.read_7bit_encoded_i32_strict()?;
+        let len = saved.len() - self.data.len();
+        if len != BinaryWriter::encoded_len_7bit_i32(value) {
+            self.data = saved;
+            return Err(BinaryReaderError::Invalid);
+        }
+        Ok(value)
+    }
 
-        loop {
-            let b = self.read_u8()?;
-            n |= ((b & 0x7f) as u64) << shift;
+    /// Reads a variable-length integer and returns the value in `i64`. The encoding must be
+    /// canonical, meaning that it must be identical to the output of
+    /// [`BinaryWriter::write_7bit_encoded_i64`] for the decoded value.
+    ///
+    /// This is the `i64` form of [`Self::read_7bit_encoded_i32_checked`]. It rejects overlong
+    /// encodings, such as `[0x81, 0x80, 0x00]` for the value 1, as well as the encodings that
+    /// [`Self::read_7bit_encoded_i64_strict`] rejects.
+    pub fn read_7bit_encoded_i64_checked(&mut self) -> Result<i64> {
+        let saved = self.data;
+        let value = self.read_7bit_encoded_i64_strict()?;
+        let len = saved.len() - self.data.len();
+        if len != BinaryWriter::encoded_len_7bit_i64(value) {
+            self.data = saved;
+            return Err(BinaryReaderError::Invalid);
+        }
+        Ok(value)
+    }
 
-            if (b & MORE) == 0 {
-                break;
+    /// Reads a .NET `TimeSpan`, which is an `i64` count of 100-nanosecond ticks, and returns it
+    /// as `Duration`.
+    ///
+    /// `Duration` cannot represent negative values, so if the tick count is negative, then this
+    /// returns `Err(BinaryReaderError::Invalid)` and the reader is not advanced.
+    pub fn read_duration_ticks(&mut self) -> Result<core::time::Duration> {
+        self.try_read(|r| {
+            let ticks = r.read_i64()?;
+            if ticks < 0 {
+                return Err(BinaryReaderError::Invalid);
             }
+            Ok(ticks_to_duration(ticks))
+        })
+    }
 
-            shift += 7;
-            if shift >= 64 {
+    /// Reads the `Ticks` value of a .NET `DateTime` in UTC and returns it as `SystemTime`. This is
+    /// the inverse of `BinaryWriter::write_systemtime_ticks`, which describes the encoding.
+    ///
+    /// If the tick count is outside of the range of `DateTime` (years 1 to 9999), or cannot be
+    /// represented as a `SystemTime` on this platform, then this returns
+    /// `Err(BinaryReaderError::Invalid)` and the reader is not advanced.
+    #[cfg(feature = "std")]
+    pub fn read_systemtime_ticks(&mut self) -> Result<std::time::SystemTime> {
+        self.try_read(|r| {
+            let ticks = r.read_i64()?;
+            if !(0..=MAX_DATETIME_TICKS).contains(&ticks) {
                 return Err(BinaryReaderError::Invalid);
             }
+
+            let offset = ticks - UNIX_EPOCH_TICKS;
+            let t = if offset >= 0 {
+                std::time::UNIX_EPOCH.checked_add(ticks_to_duration(offset))
+            } else {
+                std::time::UNIX_EPOCH.checked_sub(ticks_to_duration(-offset))
+            };
+            t.ok_or(BinaryReaderError::Invalid)
+        })
+    }
+
+    /// Reads a .NET `System.Guid` value and returns it as `uuid::Uuid`.
+    ///
+    /// .NET encodes a `Guid` (see `Guid.ToByteArray()`) as 16 bytes, but the first three fields
+    /// of the GUID are stored in little-endian byte order, while the last 8 bytes are stored in
+    /// the order they appear in the string form of the GUID. This function applies the necessary
+    /// byte swaps.
+    #[cfg(feature = "uuid")]
+    pub fn read_uuid(&mut self) -> Result<uuid::Uuid> {
+        Ok(uuid::Uuid::from_bytes_le(self.read_cbytes()?))
+    }
+
+    /// Reads the raw representation of a .NET `decimal` value, which is 4 `i32` values in the
+    /// order `lo`, `mid`, `hi`, `flags`. This matches the array returned by .NET's
+    /// `decimal.GetBits` and the encoding used by `BinaryWriter.Write(decimal)`.
+    ///
+    /// The `flags` value is not validated.
+    pub fn read_decimal_bits(&mut self) -> Result<[i32; 4]> {
+        let bytes: [u8; 16] = self.read_cbytes()?;
+        let mut bits = [0i32; 4];
+        for (b, chunk) in bits.iter_mut().zip(bytes.chunks_exact(4)) {
+            // This unwrap() call will get optimized out.
+            *b = i32::from_le_bytes(chunk.try_into().unwrap());
         }
+        Ok(bits)
+    }
+
+    /// Reads a .NET `decimal` value and converts it to `rust_decimal::Decimal`.
+    ///
+    /// If the `flags` field is not valid (the scale is greater than 28, or any of the reserved
+    /// bits are set), then this returns `Err(BinaryReaderError::Invalid)`. .NET's
+    /// `BinaryReader.ReadDecimal` rejects the same values. The reader is not advanced if this
+    /// function fails.
+    #[cfg(feature = "rust_decimal")]
+    pub fn read_rust_decimal(&mut self) -> Result<rust_decimal::Decimal> {
+        self.try_read(|r| {
+            let [lo, mid, hi, flags] = r.read_decimal_bits()?;
+            let flags = flags as u32;
+            let scale = (flags >> 16) & 0xff;
+            if flags & 0x7f00_ffff != 0 || scale > 28 {
+                return Err(BinaryReaderError::Invalid);
+            }
 
-        Ok(n as i64)
+            let mantissa =
+                (lo as u32 as i128) | ((mid as u32 as i128) << 32) | ((hi as u32 as i128) << 64);
+            let mut d = rust_decimal::Decimal::try_from_i128_with_scale(mantissa, scale)
+                .map_err(|_| BinaryReaderError::Invalid)?;
+            d.set_sign_negative(flags & 0x8000_0000 != 0);
+            Ok(d)
+        })
     }
 
     /// Reads a length-prefixed UTF-8 string.
@@ -199,8 +995,62 @@ impl<'a> BinaryReader<'a> {
     ///
     /// The caller must handle validating that the string is well-formed UTF-8, if necessary.
     pub fn read_utf8_bytes(&mut self) -> Result<&'a [u8]> {
-        let len_i32 = self.read_7bit_encoded_i32()?;
-        let Ok(len_usize) = usize::try_from(len_i32) else {
+        self.read_length_prefixed_bytes()
+    }
+
+    /// Reads a length-prefixed sequence of bytes. The length is encoded as a 7-bit encoded `i32`.
+    ///
+    /// This does not copy any data. It returns a slice reference to the bytes.
+    pub fn read_length_prefixed_bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.read_7bit_encoded_len()?;
+        self.read_bytes(len)
+    }
+
+    /// Reads a sequence of bytes that is preceded by its length as a `u16` in little-endian byte
+    /// order. This is the inverse of `BinaryWriter::write_blob_u16_prefixed`.
+    ///
+    /// This does not copy any data. The reader is not advanced if this function fails.
+    pub fn read_blob_u16_prefixed(&mut self) -> Result<&'a [u8]> {
+        self.try_read(|r| {
+            let len = r.read_u16()?;
+            r.read_bytes(len as usize)
+        })
+    }
+
+    /// Reads a sequence of bytes that is preceded by its length as a `u32` in little-endian byte
+    /// order. This is the inverse of `BinaryWriter::write_blob_u32_prefixed`.
+    ///
+    /// This does not copy any data. The reader is not advanced if this function fails.
+    pub fn read_blob_u32_prefixed(&mut self) -> Result<&'a [u8]> {
+        self.try_read(|r| {
+            let len = r.read_u32()?;
+            let len = usize::try_from(len).map_err(|_| BinaryReaderError::Invalid)?;
+            r.read_bytes(len)
+        })
+    }
+
+    /// Reads a length-prefixed sequence of bytes and copies it into a new `Vec<u8>`.
+    ///
+    /// The length is validated against the input data before any memory is allocated. If the
+    /// length is larger than the limit set by [`Self::set_max_count`], then this returns
+    /// `Err(BinaryReaderError::Invalid)`.
+    pub fn read_length_prefixed_bytes_vec(&mut self) -> Result<Vec<u8>> {
+        let len = self.read_7bit_encoded_len()?;
+        Ok(self.read_bytes_max(len, self.max_count)?.to_vec())
+    }
+
+    /// Reads a UTF-8 string whose length prefix is a 7-bit encoded `i64`, rather than `i32`.
+    ///
+    /// **This is not compatible with .NET's `BinaryWriter`**, which always uses a 7-bit encoded
+    /// `i32` for string lengths. Some serializers use this extended form for strings that are
+    /// larger than `i32::MAX` bytes. Only use this function if you know that the data was written
+    /// using the extended form.
+    ///
+    /// This does not copy any data. The caller must handle validating that the string is
+    /// well-formed UTF-8, if necessary.
+    pub fn read_utf8_bytes_long(&mut self) -> Result<&'a [u8]> {
+        let len_i64 = self.read_7bit_encoded_i64()?;
+        let Ok(len_usize) = usize::try_from(len_i64) else {
             return Err(BinaryReaderError::Invalid);
         };
 
@@ -226,6 +1076,8 @@ impl<'a> BinaryReader<'a> {
     ///
     /// This does not copy any data. It reads the prefixed length, locates the contents of the
     /// string, validates that the contents are well-formed UTF-8 and returns the string slice.
+    /// If the contents are not well-formed UTF-8, then this returns
+    /// `Err(BinaryReaderError::Invalid)`.
     ///
     /// The encoded stream does not contain any information that distinguishes UTF-8 strings and
     /// UTF-16 strings, so applications will need to make sure that they call the correct
@@ -235,10 +1087,63 @@ impl<'a> BinaryReader<'a> {
         if let Ok(s) = core::str::from_utf8(bytes) {
             Ok(s)
         } else {
-            Err(BinaryReaderError::NeedsMoreData)
+            Err(BinaryReaderError::Invalid)
         }
     }
 
+    /// Reads a length-prefixed UTF-8 string and removes any NUL characters at the end of it.
+    ///
+    /// This is for formats that store strings in fixed-size fields, where a string that is
+    /// shorter than its field is padded with NUL bytes. Only trailing NUL bytes are removed; NUL
+    /// bytes within the string are preserved. The bytes are validated as UTF-8 after the NUL
+    /// bytes are removed. If they are not well-formed UTF-8, then this returns
+    /// `Err(BinaryReaderError::Invalid)`.
+    pub fn read_utf8_str_trim_nuls(&mut self) -> Result<&'a str> {
+        let mut bytes = self.read_utf8_bytes()?;
+        while let [rest @ .., 0] = bytes {
+            bytes = rest;
+        }
+        core::str::from_utf8(bytes).map_err(|_| BinaryReaderError::Invalid)
+    }
+
+    /// Reads a UTF-8 string whose length (in bytes) is `byte_len`. The string is not
+    /// length-prefixed; this is for formats where the length is stored elsewhere.
+    ///
+    /// If the input is shorter than `byte_len`, then this returns
+    /// `Err(BinaryReaderError::NeedsMoreData { .. })`. If the bytes are not well-formed UTF-8,
+    /// then this returns `Err(BinaryReaderError::Invalid)`. In both cases, the reader is not
+    /// advanced.
+    pub fn read_utf8_str_exact(&mut self, byte_len: usize) -> Result<&'a str> {
+        let Some(bytes) = self.data.get(..byte_len) else {
+            return Err(BinaryReaderError::NeedsMoreData {
+                needed: byte_len - self.data.len(),
+            });
+        };
+
+        let s = core::str::from_utf8(bytes).map_err(|_| BinaryReaderError::Invalid)?;
+        self.data = &self.data[byte_len..];
+        Ok(s)
+    }
+
+    /// Reads a length-prefixed UTF-8 string and converts it to UTF-16 code units.
+    ///
+    /// The input string is required to be well-formed UTF-8; if it is not, then this function
+    /// returns `Err(BinaryReaderError::Invalid)`. This is useful for passing strings to APIs that
+    /// expect UTF-16, without building an intermediate `String`.
+    pub fn read_utf8_to_utf16(&mut self) -> Result<Vec<u16>> {
+        Ok(self.read_utf8_str()?.encode_utf16().collect())
+    }
+
+    /// Reads a length-prefixed UTF-8 string and returns it as `Cow<str>`.
+    ///
+    /// The input string is required to be well-formed UTF-8; if it is not, then this function
+    /// returns `Err(BinaryReaderError::Invalid)`. Unlike `read_utf8_string_lossy`, this
+    /// function never replaces invalid sequences. Because valid UTF-8 can always be borrowed from
+    /// the input, this function currently always returns `Cow::Borrowed`.
+    pub fn read_utf8_str_cow(&mut self) -> Result<Cow<'a, str>> {
+        Ok(Cow::Borrowed(self.read_utf8_str()?))
+    }
+
     /// Reads a length-prefixed UTF-8 string and returns it as `Cow<str>`.
     ///
     /// The input string is expected to be valid UTF-8. However, if the input contains byte
@@ -254,6 +1159,52 @@ impl<'a> BinaryReader<'a> {
         Ok(String::from_utf8_lossy(bytes))
     }
 
+    /// Reads an optional UTF-8 string, which is encoded as a `bool` followed by the
+    /// length-prefixed string if the `bool` is `true`. This is the inverse of
+    /// `BinaryWriter::write_option_utf8_str`.
+    ///
+    /// The reader is not advanced if this function fails.
+    #[cfg(feature = "std")]
+    pub fn read_option_utf8_string(&mut self) -> Result<Option<String>> {
+        self.try_read(|r| {
+            if r.read_bool()? {
+                Ok(Some(r.read_utf8_str()?.to_string()))
+            } else {
+                Ok(None)
+            }
+        })
+    }
+
+    /// Reads a count-prefixed list of UTF-8 strings, which is how .NET code commonly writes
+    /// `string[]`. The count is encoded as a 7-bit encoded `i32`, and each string is
+    /// length-prefixed. This is the inverse of `BinaryWriter::write_utf8_string_list`.
+    ///
+    /// The count is checked against the limit set by [`Self::set_max_count`], as in
+    /// [`Self::read_vec`]. If any string is not well-formed UTF-8, then this returns
+    /// `Err(BinaryReaderError::Invalid)`.
+    #[cfg(feature = "std")]
+    pub fn read_utf8_string_list(&mut self) -> Result<Vec<String>> {
+        self.read_vec(|r| Ok(r.read_utf8_str()?.to_string()))
+    }
+
+    /// Reads a count-prefixed list of key/value pairs of UTF-8 strings, which is a common
+    /// encoding of `Dictionary<string, string>`. The count is encoded as a 7-bit encoded `i32`,
+    /// and each entry is a length-prefixed key followed by a length-prefixed value. This is the
+    /// inverse of `BinaryWriter::write_string_map`.
+    ///
+    /// The entries are returned in the order they were encoded. This does not check for
+    /// duplicate keys. The count is checked against the limit set by [`Self::set_max_count`], as
+    /// in [`Self::read_vec`]. If any string is not well-formed UTF-8, then this returns
+    /// `Err(BinaryReaderError::Invalid)`.
+    #[cfg(feature = "std")]
+    pub fn read_string_map(&mut self) -> Result<Vec<(String, String)>> {
+        self.read_vec(|r| {
+            let key = r.read_utf8_str()?.to_string();
+            let value = r.read_utf8_str()?.to_string();
+            Ok((key, value))
+        })
+    }
+
     /// Reads a length-prefixed UTF-16 string and returns it as `&[U16<LE>]`.
     ///
     /// This does not copy any data. It reads the prefixed length, locates the contents of the
@@ -263,28 +1214,50 @@ impl<'a> BinaryReader<'a> {
     /// The caller is responsible for converting the returned slice to a different, more usable
     /// form.
     pub fn read_utf16_wchars(&mut self) -> Result<&'a [U16<LE>]> {
-        let bytes_len_i32 = self.read_7bit_encoded_i32()?;
-        let Ok(bytes_len_usize) = usize::try_from(bytes_len_i32) else {
+        let bytes = self.read_length_prefixed_bytes()?;
+
+        let Ok(wchars) = <[U16<LE>]>::ref_from_bytes(bytes) else {
             return Err(BinaryReaderError::Invalid);
         };
 
-        let bytes = self.read_bytes(bytes_len_usize)?;
+        Ok(wchars)
+    }
 
-        let Ok(wchars) = <[U16<LE>]>::ref_from_bytes(bytes) else {
+    /// Reads a length-prefixed UTF-16BE (big-endian) string and returns it as `&[U16<BE>]`.
+    ///
+    /// .NET's `BinaryWriter` always writes UTF-16 strings in little-endian byte order, so this
+    /// is only needed for inputs produced by other writers. The length prefix is the same as
+    /// for [`Self::read_utf16_wchars`].
+    pub fn read_utf16_wchars_be(&mut self) -> Result<&'a [U16<BE>]> {
+        let bytes = self.read_length_prefixed_bytes()?;
+
+        let Ok(wchars) = <[U16<BE>]>::ref_from_bytes(bytes) else {
             return Err(BinaryReaderError::Invalid);
         };
 
         Ok(wchars)
     }
 
+    /// Reads a length-prefixed UTF-16 string and stores its code units in `buf`.
+    ///
+    /// `buf` is cleared before the code units are appended. This allows the same buffer to be
+    /// reused for many strings, which avoids allocating memory for each string. The caller is
+    /// responsible for validating or converting the code units.
+    pub fn read_utf16_wchars_into(&mut self, buf: &mut Vec<u16>) -> Result<()> {
+        let wchars = self.read_utf16_wchars()?;
+        buf.clear();
+        buf.extend(wchars.iter().map(|c| c.get()));
+        Ok(())
+    }
+
     /// Reads a length-prefixed UTF-16 string and returns it as `String`.
     ///
     /// The input string is required to be well-formed UTF-16; if it contains illegal UTF-16 code
     /// points or illegal surrogate sequences, then this function will return
-    /// `Err(ReaderError::Invalid)`.
+    /// `Err(BinaryReaderError::Invalid)`.
     ///
     /// The length in bytes of the string is required to be a multiple of 2. If it is not, then
-    /// this function will return `Err(ReaderError::Invalid)`.
+    /// this function will return `Err(BinaryReaderError::Invalid)`.
     ///
     /// The encoded stream does not contain any information that distinguishes UTF-8 strings and
     /// UTF-16 strings, so applications will need to make sure that they call the correct
@@ -296,6 +1269,43 @@ impl<'a> BinaryReader<'a> {
         String::from_utf16(&wchars_u16).map_err(|_| BinaryReaderError::Invalid)
     }
 
+    /// Reads a length-prefixed UTF-16 string that may begin with a byte-order mark (BOM), and
+    /// returns it as `String` without the BOM.
+    ///
+    /// If the first code unit is U+FEFF, then it is removed and the rest of the string is decoded
+    /// as little-endian. If the first code unit is U+FFFE (a BOM in big-endian byte order), then
+    /// it is removed and the rest of the string is decoded as big-endian. Otherwise, this is the
+    /// same as [`Self::read_utf16_string`].
+    #[cfg(feature = "std")]
+    pub fn read_utf16_string_strip_bom(&mut self) -> Result<String> {
+        let wchars = self.read_utf16_wchars()?;
+        let mut wchars_u16: Vec<u16> = wchars.iter().map(|c| c.get()).collect();
+        match wchars_u16.first() {
+            Some(0xfeff) => {
+                wchars_u16.remove(0);
+            }
+            Some(0xfffe) => {
+                wchars_u16.remove(0);
+                for c in wchars_u16.iter_mut() {
+                    *c = c.swap_bytes();
+                }
+            }
+            _ => {}
+        }
+        String::from_utf16(&wchars_u16).map_err(|_| BinaryReaderError::Invalid)
+    }
+
+    /// Reads a length-prefixed UTF-16BE (big-endian) string and returns it as `String`.
+    ///
+    /// The input string is required to be well-formed UTF-16; if it is not, then this function
+    /// will return `Err(BinaryReaderError::Invalid)`. See [`Self::read_utf16_wchars_be`].
+    #[cfg(feature = "std")]
+    pub fn read_utf16_string_be(&mut self) -> Result<String> {
+        let wchars = self.read_utf16_wchars_be()?;
+        let wchars_u16: Vec<u16> = wchars.iter().map(|c| c.get()).collect();
+        String::from_utf16(&wchars_u16).map_err(|_| BinaryReaderError::Invalid)
+    }
+
     /// Reads a length-prefixed UTF-16 string and returns it as `String`.
     ///
     /// If the input sequence contains illegal UTF-16 code points or illegal surrogate sequences,
@@ -303,13 +1313,334 @@ impl<'a> BinaryReader<'a> {
     /// character.
     ///
     /// The length in bytes of the string is required to be a multiple of 2. If it is not, then
-    /// this function will return `Err(ReaderError::Invalid)`.
+    /// this function will return `Err(BinaryReaderError::Invalid)`.
     #[cfg(feature = "std")]
     pub fn read_utf16_string_lossy(&mut self) -> Result<String> {
         let wchars = self.read_utf16_wchars()?;
         let wchars_u16: Vec<u16> = wchars.iter().map(|c| c.get()).collect();
         Ok(String::from_utf16_lossy(&wchars_u16))
     }
+
+    /// Reads a length-prefixed Latin-1 (ISO-8859-1) string and returns it as `String`. This
+    /// matches the behavior of a .NET `BinaryReader` that uses `Encoding.Latin1`.
+    ///
+    /// Each byte is decoded as a single character in the range U+0000 to U+00FF, so this function
+    /// cannot fail due to malformed string contents.
+    #[cfg(feature = "std")]
+    pub fn read_latin1_string(&mut self) -> Result<String> {
+        let bytes = self.read_length_prefixed_bytes()?;
+        Ok(bytes.iter().map(|&b| b as char).collect())
+    }
+
+    /// Reads a length-prefixed ASCII string and returns it as `String`. This matches the behavior
+    /// of a .NET `BinaryReader` that uses `Encoding.ASCII`, except that bytes outside of the
+    /// ASCII range are rejected rather than replaced.
+    ///
+    /// If any byte in the string is not in the ASCII range (0x00 to 0x7F), then this function
+    /// returns `Err(BinaryReaderError::Invalid)`.
+    #[cfg(feature = "std")]
+    pub fn read_ascii_string(&mut self) -> Result<String> {
+        let bytes = self.read_length_prefixed_bytes()?;
+        if !bytes.is_ascii() {
+            return Err(BinaryReaderError::Invalid);
+        }
+        Ok(bytes.iter().map(|&b| b as char).collect())
+    }
+
+    /// Reads a null-terminated string. This function returns the bytes before the NUL
+    /// terminator; the terminator is consumed but is not included in the returned slice.
+    ///
+    /// If the input does not contain a NUL terminator, then this function returns
+    /// `Err(BinaryReaderError::NeedsMoreData { .. })` and the reader is not advanced.
+    ///
+    /// The caller must handle validating that the string is well-formed UTF-8, if necessary.
+    pub fn read_cstr(&mut self) -> Result<&'a [u8]> {
+        self.read_until(0)
+    }
+
+    /// Reads bytes up to the first occurrence of `delim`. This function returns the bytes before
+    /// the delimiter; the delimiter is consumed but is not included in the returned slice.
+    ///
+    /// If the input does not contain `delim`, then this function returns
+    /// `Err(BinaryReaderError::NeedsMoreData { .. })` and the reader is not advanced.
+    pub fn read_until(&mut self, delim: u8) -> Result<&'a [u8]> {
+        let Some(len) = self.data.iter().position(|&b| b == delim) else {
+            return Err(BinaryReaderError::NeedsMoreData { needed: 1 });
+        };
+
+        let s = &self.data[..len];
+        self.data = &self.data[len + 1..];
+        Ok(s)
+    }
+
+    /// Returns an iterator that reads the remaining bytes one at a time. Each byte is consumed as
+    /// it is returned, and the iterator stops at the end of the input.
+    ///
+    /// This is convenient for byte-oriented scanning, such as computing a checksum. If the
+    /// iterator is dropped early, the reader is positioned after the last byte returned.
+    pub fn bytes(&mut self) -> Bytes<'_, 'a> {
+        Bytes { reader: self }
+    }
+
+    /// Calls `f` with this reader and, if `f` fails, restores the reader to the position it had
+    /// before `f` was called.
+    ///
+    /// This is useful for speculative parsing of values that are made of several fields. Even
+    /// if `f` has already read some of the fields when it fails, the reader is left where it
+    /// started, so the caller can retry (for example, after more data is available) or try a
+    /// different parse.
+    pub fn try_read<U, F>(&mut self, f: F) -> Result<U>
+    where
+        F: FnOnce(&mut Self) -> Result<U>,
+    {
+        let saved = self.data;
+        let result = f(self);
+        if result.is_err() {
+            self.data = saved;
+        }
+        result
+    }
+
+    /// Reads two values, in order, and returns them as a tuple.
+    ///
+    /// `fa` and `fb` are called with this reader, so they can use any of the `read_*` methods.
+    /// If `fa` fails, then `fb` is not called.
+    pub fn read_pair<A, B, FA, FB>(&mut self, fa: FA, fb: FB) -> Result<(A, B)>
+    where
+        FA: FnOnce(&mut Self) -> Result<A>,
+        FB: FnOnce(&mut Self) -> Result<B>,
+    {
+        let a = fa(self)?;
+        let b = fb(self)?;
+        Ok((a, b))
+    }
+
+    /// Skips zero padding until [`Self::position`] is a multiple of `align`, and then calls `f`
+    /// to read a value. This is useful for arrays of records where each record must start at an
+    /// aligned offset.
+    ///
+    /// See [`Self::skip_padding_to_multiple`] for how padding is checked. If `f` fails, the
+    /// padding has already been consumed; use [`Self::try_read`] to restore the position.
+    ///
+    /// If `align` is zero, then this returns `Err(BinaryReaderError::Invalid)` without calling
+    /// `f`.
+    pub fn read_aligned<U, F>(&mut self, align: usize, f: F) -> Result<U>
+    where
+        F: FnOnce(&mut Self) -> Result<U>,
+    {
+        self.skip_padding_to_multiple(align)?;
+        f(self)
+    }
+
+    /// Reads a count-prefixed sequence of values. The count is encoded as a 7-bit encoded `i32`,
+    /// and then `f` is called once for each element.
+    ///
+    /// If the count is larger than the limit set by [`Self::set_max_count`], then this returns
+    /// `Err(BinaryReaderError::Invalid)`. The count comes from the input, so this function does
+    /// not trust it for sizing the allocation; at most 4096 elements are preallocated, and the
+    /// vector grows normally after that.
+    pub fn read_vec<U, F>(&mut self, mut f: F) -> Result<Vec<U>>
+    where
+        F: FnMut(&mut Self) -> Result<U>,
+    {
+        let count = self.read_7bit_encoded_len()?;
+        if count > self.max_count {
+            return Err(BinaryReaderError::Invalid);
+        }
+
+        let mut values = Vec::with_capacity(count.min(MAX_PREALLOC_COUNT));
+        for _ in 0..count {
+            values.push(f(self)?);
+        }
+        Ok(values)
+    }
+
+    /// Returns a hex dump of the remaining input data. This is intended for diagnostics, such as
+    /// logging the input when parsing fails.
+    #[cfg(feature = "pretty-hex")]
+    pub fn debug_hex(&self) -> alloc::string::String {
+        pretty_hex::pretty_hex(&self.data)
+    }
+}
+
+/// An iterator that reads the remaining bytes of a [`BinaryReader`] one at a time. This is
+/// returned by [`BinaryReader::bytes`].
+pub struct Bytes<'r, 'a> {
+    reader: &'r mut BinaryReader<'a>,
+}
+
+impl Iterator for Bytes<'_, '_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        self.reader.read_u8().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.reader.data.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Bytes<'_, '_> {}
+
+/// A [`BinaryReader`] over the unread portion of a `std::io::Cursor`, created by
+/// [`BinaryReader::from_cursor`].
+///
+/// This dereferences to the `BinaryReader`, so values can be read from it directly. Call
+/// [`Self::commit`] to get the position that the cursor should be advanced to.
+#[cfg(feature = "std")]
+pub struct CursorReader<'a> {
+    /// The reader over the cursor's buffer.
+    pub reader: BinaryReader<'a>,
+}
+
+#[cfg(feature = "std")]
+impl CursorReader<'_> {
+    /// Finishes reading and returns the cursor position just past the data that has been
+    /// consumed. Pass this to `Cursor::set_position` to advance the cursor.
+    #[must_use]
+    pub fn commit(self) -> u64 {
+        self.reader.position() as u64
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> core::ops::Deref for CursorReader<'a> {
+    type Target = BinaryReader<'a>;
+
+    fn deref(&self) -> &BinaryReader<'a> {
+        &self.reader
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> core::ops::DerefMut for CursorReader<'a> {
+    fn deref_mut(&mut self) -> &mut BinaryReader<'a> {
+        &mut self.reader
+    }
+}
+
+/// Converts a non-negative count of 100-nanosecond ticks to `Duration`.
+fn ticks_to_duration(ticks: i64) -> core::time::Duration {
+    let secs = (ticks / TICKS_PER_SECOND) as u64;
+    let nanos = (ticks % TICKS_PER_SECOND) as u32 * 100;
+    core::time::Duration::new(secs, nanos)
+}
+
+/// Incremental decoder for 7-bit encoded integers.
+///
+/// This holds the partially-decoded value between calls to [`VarIntDecoder::push`], so that
+/// a varint which is split across several input buffers can be decoded one byte at a time
+/// without losing progress. It applies the same rules as
+/// [`BinaryReader::read_7bit_encoded_i32`] and [`BinaryReader::read_7bit_encoded_i64`], or the
+/// rules of their `_strict` forms if it is created with [`VarIntDecoder::new_i32_strict`] or
+/// [`VarIntDecoder::new_i64_strict`].
+#[derive(Clone, Debug)]
+pub struct VarIntDecoder {
+    value: u64,
+    shift: u32,
+    bits: u32,
+    /// Whether the unused bits of the last byte must be zero.
+    strict: bool,
+    /// The result of the final call to `push`, once decoding has completed or failed.
+    done: Option<Result<bool>>,
+}
+
+impl VarIntDecoder {
+    /// Creates a decoder for a 7-bit encoded `i32` (at most 5 bytes).
+    pub fn new_i32() -> Self {
+        Self {
+            value: 0,
+            shift: 0,
+            bits: 32,
+            strict: false,
+            done: None,
+        }
+    }
+
+    /// Creates a decoder for a 7-bit encoded `i64` (at most 10 bytes).
+    pub fn new_i64() -> Self {
+        Self {
+            value: 0,
+            shift: 0,
+            bits: 64,
+            strict: false,
+            done: None,
+        }
+    }
+
+    /// Creates a decoder for a 7-bit encoded `i32` which applies the same rules as
+    /// [`BinaryReader::read_7bit_encoded_i32_strict`]. The 5th byte may only contain 4
+    /// meaningful bits.
+    pub fn new_i32_strict() -> Self {
+        Self {
+            strict: true,
+            ..Self::new_i32()
+        }
+    }
+
+    /// Creates a decoder for a 7-bit encoded `i64` which applies the same rules as
+    /// [`BinaryReader::read_7bit_encoded_i64_strict`]. The 10th byte may only contain 1
+    /// meaningful bit.
+    pub fn new_i64_strict() -> Self {
+        Self {
+            strict: true,
+            ..Self::new_i64()
+        }
+    }
+
+    /// Feeds the next byte of the encoded integer to the decoder.
+    ///
+    /// Returns `Ok(true)` if this was the last byte of the integer, in which case the value
+    /// can be retrieved with [`Self::value_i32`] or [`Self::value_i64`]. Returns `Ok(false)`
+    /// if more bytes are needed. Returns `BinaryReaderError::Invalid` if the encoding is
+    /// longer than the maximum length for the integer type, or if this is a strict decoder and
+    /// the last byte has bits set beyond the width of the integer type.
+    ///
+    /// Once this has returned `Ok(true)` or an error, the decoder is finished. Further calls
+    /// ignore `b`, do not change the decoded value, and return the same result again.
+    pub fn push(&mut self, b: u8) -> Result<bool> {
+        const MORE: u8 = 0x80;
+
+        if let Some(done) = &self.done {
+            return done.clone();
+        }
+
+        if self.strict && self.shift + 7 >= self.bits {
+            // This must be the last byte, and it may only contain the remaining bits.
+            let last_max = (1u8 << (self.bits - self.shift)) - 1;
+            if b > last_max {
+                self.done = Some(Err(BinaryReaderError::Invalid));
+                return Err(BinaryReaderError::Invalid);
+            }
+        }
+
+        self.value |= ((b & 0x7f) as u64) << self.shift;
+
+        if (b & MORE) == 0 {
+            self.done = Some(Ok(true));
+            return Ok(true);
+        }
+
+        self.shift += 7;
+        if self.shift >= self.bits {
+            self.done = Some(Err(BinaryReaderError::Invalid));
+            return Err(BinaryReaderError::Invalid);
+        }
+
+        Ok(false)
+    }
+
+    /// Returns the decoded value as `i32`. Bits beyond the low 32 bits are discarded.
+    pub fn value_i32(&self) -> i32 {
+        self.value as u32 as i32
+    }
+
+    /// Returns the decoded value as `i64`.
+    pub fn value_i64(&self) -> i64 {
+        self.value as i64
+    }
 }
 
 /// Error type for `BinaryReader`
@@ -319,12 +1650,34 @@ pub enum BinaryReaderError {
     /// reading the input.
     ///
     /// If a function returns this error value, then the encoded value may still be well-formed,
-    /// if the rest of the data can be read. However, most of the `read_*` functions _do not_
-    /// guarantee that they don't advance the read position, even if they return `EndOfData`.
-    NeedsMoreData,
+    /// if the rest of the data can be read.
+    ///
+    /// The fixed-size reads (`read_u8` through `read_f64`, `read_bool`, `read_char`,
+    /// `read_cbytes`, `read_bytes`, `read_slice`, and similar) and the 7-bit encoded integer
+    /// reads (`read_7bit_encoded_*`, including the `_strict` and `_checked` forms, and
+    /// `skip_7bit_encoded`) are guaranteed not to advance the read position when they return
+    /// `NeedsMoreData`.
+    /// Methods that read a length prefix followed by the data it describes (such as
+    /// `read_utf8_str`, `read_utf16_wchars`, and `read_length_prefixed_bytes`) may consume the
+    /// length prefix before returning `NeedsMoreData`, so callers should restore `data` before
+    /// retrying them.
+    NeedsMoreData {
+        /// The minimum number of additional bytes that are needed to decode the value.
+        ///
+        /// For fixed-size values, this is exact. For variable-length values (such as a 7-bit
+        /// encoded integer), the total length is not known until the value is decoded, so this
+        /// only describes the data needed by the current step of decoding. It is always at
+        /// least 1.
+        needed: usize,
+    },
 
     /// The `read_*` request found invalid data in the input. The input is malformed.
     Invalid,
+
+    /// The input was expected to be fully consumed, but there is data remaining after the end of
+    /// the message. Some protocols legitimately pad their messages, so this is distinct from
+    /// `Invalid`.
+    TrailingData,
 }
 
 impl core::error::Error for BinaryReaderError {}
@@ -332,12 +1685,17 @@ impl core::error::Error for BinaryReaderError {}
 impl core::fmt::Display for BinaryReaderError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::NeedsMoreData => f.write_str(
-                "The value could not be decoded because the input data was not complete.",
+            Self::NeedsMoreData { needed } => write!(
+                f,
+                "The value could not be decoded because the input data was not complete. \
+                 At least {needed} more byte(s) are needed."
             ),
             Self::Invalid => {
                 f.write_str("The value could not be decoded because the input data is malformed.")
             }
+            Self::TrailingData => {
+                f.write_str("The input data contains data after the end of the message.")
+            }
         }
     }
 }
@@ -1,31 +1,137 @@
-use zerocopy::IntoBytes;
+use crate::{BinaryReader, BinaryReaderError};
+use zerocopy::{Immutable, IntoBytes};
 
 extern crate alloc;
 use alloc::vec::Vec;
+use core::borrow::{Borrow, BorrowMut};
 
 pub type Result<T> = core::result::Result<T, BinaryWriterError>;
 
+/// The number of .NET ticks (100-nanosecond intervals) in one second.
+pub(crate) const TICKS_PER_SECOND: i64 = 10_000_000;
+
+/// The value of `DateTime.Ticks` for the Unix epoch, 1970-01-01 00:00:00 UTC.
+#[cfg(feature = "std")]
+pub(crate) const UNIX_EPOCH_TICKS: i64 = 621_355_968_000_000_000;
+
+/// The value of `DateTime.MaxValue.Ticks`, which is 9999-12-31 23:59:59.9999999.
+#[cfg(feature = "std")]
+pub(crate) const MAX_DATETIME_TICKS: i64 = 3_155_378_975_999_999_999;
+
 /// Encodes binary values, using the same rules as .NET's `System.IO.BinaryWriter`.
-pub struct BinaryWriter {
+///
+/// The `write_*` methods that cannot fail return `&mut Self`, so calls can be chained, e.g.
+/// `w.write_u8(1).write_u16(2).write_u32(3)`. The methods that can fail (such as the string
+/// methods) return `Result`.
+///
+/// The `write_*` methods are available when the output is a `Vec<u8>` (the default) or a
+/// `&mut Vec<u8>`. Use `BinaryWriter::wrap(&mut buf)` to append to a buffer that is owned
+/// elsewhere, without moving it into the writer.
+/// When the `std` feature is enabled, a `BinaryWriter` can also wrap any `Write` stream, such
+/// as a `File` or `Cursor`, for writing framed data with `BinaryWriter::write_framed`.
+#[derive(Clone)]
+pub struct BinaryWriter<W = Vec<u8>> {
     /// The output data.
-    pub out: Vec<u8>,
+    pub out: W,
 }
 
-impl BinaryWriter {
-    /// Constructor
-    pub fn wrap(out: Vec<u8>) -> Self {
+impl Default for BinaryWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shows the length of the output and a hex preview of its first bytes, rather than every byte.
+impl<W: Borrow<Vec<u8>>> core::fmt::Debug for BinaryWriter<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let out: &[u8] = self.out.borrow();
+        f.debug_struct("BinaryWriter")
+            .field("len", &out.len())
+            .field("out", &HexPreview(out))
+            .finish()
+    }
+}
+
+/// Formats up to `HexPreview::MAX_LEN` bytes as hex, followed by `...` if there are more.
+struct HexPreview<'a>(&'a [u8]);
+
+impl HexPreview<'_> {
+    const MAX_LEN: usize = 16;
+}
+
+impl core::fmt::Debug for HexPreview<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("[")?;
+        for (i, b) in self.0.iter().take(Self::MAX_LEN).enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{b:02x}")?;
+        }
+        if self.0.len() > Self::MAX_LEN {
+            f.write_str(" ...")?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl<W> BinaryWriter<W> {
+    /// Constructor. New data is appended to `out`; any existing contents are preserved.
+    pub fn wrap(out: W) -> Self {
         Self { out }
     }
 
     /// Extracts the inner buffer
-    pub fn into_inner(self) -> Vec<u8> {
+    pub fn into_inner(self) -> W {
         self.out
     }
 
     /// Accesses the inner buffer
-    pub fn inner_mut(&mut self) -> &mut Vec<u8> {
+    pub fn inner_mut(&mut self) -> &mut W {
         &mut self.out
     }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> BinaryWriter<W> {
+    /// Writes a nested, length-prefixed block to a stream. `body` writes the contents of the
+    /// block into a temporary `BinaryWriter`, so all of the `write_*` methods are available to
+    /// it. The contents are then written to the stream, preceded by their length (encoded as a
+    /// 7-bit encoded `i32`).
+    ///
+    /// The output is identical to the output of [`BinaryWriter::write_subframe`], so the block
+    /// can be read with `BinaryReader::read_exact_subframe`. Nested blocks can be written with
+    /// `write_subframe` from within `body`.
+    ///
+    /// If `body` fails, or if the block is too long to encode, then this returns an error with
+    /// kind `InvalidInput` and nothing is written to the stream. If writing to the stream fails,
+    /// then part of the block may have been written.
+    pub fn write_framed<F>(&mut self, body: F) -> std::io::Result<()>
+    where
+        F: FnOnce(&mut BinaryWriter) -> Result<()>,
+    {
+        let invalid_input = |e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e);
+
+        let mut inner = BinaryWriter::new();
+        body(&mut inner).map_err(invalid_input)?;
+        let len_i32 = i32::try_from(inner.len())
+            .map_err(|_| invalid_input(BinaryWriterError::CannotEncode))?;
+
+        let mut prefix = BinaryWriter::with_capacity(5);
+        prefix.write_7bit_encoded_i32(len_i32);
+        self.out.write_all(&prefix.out)?;
+        self.out.write_all(&inner.out)
+    }
+}
+
+impl BinaryWriter {
+    /// Creates a `BinaryWriter` that appends to an existing buffer. Any existing contents of
+    /// `buf` (such as a header) are preserved, and new data is written after them.
+    ///
+    /// This is equivalent to [`Self::wrap`].
+    pub fn from_vec(buf: Vec<u8>) -> Self {
+        Self::wrap(buf)
+    }
 
     /// Creates a new `BinaryWriter` over a `Vec<u8>`
     pub fn new() -> Self {
@@ -39,63 +145,280 @@ impl BinaryWriter {
         }
     }
 
+    /// Returns the number of bytes that [`Self::write_7bit_encoded_i32`] will write for `value`.
+    /// The result is in the range 1 to 5. All negative values use 5 bytes.
+    pub fn encoded_len_7bit_i32(value: i32) -> usize {
+        let n = value as u32;
+        let bits = 32 - n.leading_zeros() as usize;
+        bits.max(1).div_ceil(7)
+    }
+
+    /// Returns the number of bytes that [`Self::write_7bit_encoded_i64`] will write for `value`.
+    /// The result is in the range 1 to 10. All negative values use 10 bytes.
+    pub fn encoded_len_7bit_i64(value: i64) -> usize {
+        let n = value as u64;
+        let bits = 64 - n.leading_zeros() as usize;
+        bits.max(1).div_ceil(7)
+    }
+
+    /// Computes the length prefix (in bytes) for a UTF-16 string that contains
+    /// `num_code_units` code units.
+    pub(crate) fn utf16_len_prefix(num_code_units: usize) -> Result<i32> {
+        num_code_units
+            .checked_mul(2)
+            .and_then(|len| i32::try_from(len).ok())
+            .ok_or(BinaryWriterError::CannotEncode)
+    }
+}
+
+impl<W: BorrowMut<Vec<u8>>> BinaryWriter<W> {
+    /// Returns the output buffer.
+    fn buf(&mut self) -> &mut Vec<u8> {
+        self.out.borrow_mut()
+    }
+
+    /// Reserves capacity for at least `additional` more bytes in the output buffer.
+    pub fn reserve(&mut self, additional: usize) {
+        self.buf().reserve(additional);
+    }
+
+    /// Returns the number of bytes that have been written.
+    pub fn len(&self) -> usize {
+        self.out.borrow().len()
+    }
+
+    /// Returns `true` if no bytes have been written.
+    pub fn is_empty(&self) -> bool {
+        self.out.borrow().is_empty()
+    }
+
+    /// Shortens the output to `len` bytes, discarding anything written after it. If `len` is
+    /// greater than or equal to the current length, this has no effect.
+    ///
+    /// Together with [`Self::len`], this can be used to roll back a partially-written record:
+    /// save the length before writing the record, and truncate to it if writing fails.
+    pub fn truncate(&mut self, len: usize) {
+        self.buf().truncate(len);
+    }
+
     /// Writes `bytes` to the output.
-    pub fn write_bytes(&mut self, bytes: &[u8]) {
-        self.out.extend_from_slice(bytes);
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.buf().extend_from_slice(bytes);
+        self
+    }
+
+    /// Writes the bytes produced by `iter`. This avoids collecting the bytes into a temporary
+    /// buffer first.
+    pub fn write_iter<I: IntoIterator<Item = u8>>(&mut self, iter: I) -> &mut Self {
+        self.buf().extend(iter);
+        self
+    }
+
+    /// Reads `len` bytes from `reader` and writes them to the output, without interpreting them.
+    /// This is useful for passing opaque regions through when re-serializing data.
+    ///
+    /// If `reader` contains fewer than `len` bytes, then this returns the error from
+    /// `reader` (`BinaryReaderError::NeedsMoreData { .. }`), and neither `reader` nor the
+    /// output is modified.
+    pub fn copy_from(
+        &mut self,
+        reader: &mut BinaryReader<'_>,
+        len: usize,
+    ) -> core::result::Result<(), BinaryReaderError> {
+        let bytes = reader.read_bytes(len)?;
+        self.write_bytes(bytes);
+        Ok(())
+    }
+
+    /// Writes `len` zero bytes. This is useful for reserved or padding fields.
+    pub fn write_zeros(&mut self, len: usize) -> &mut Self {
+        self.write_repeated(0, len)
+    }
+
+    /// Writes `count` copies of `byte`.
+    pub fn write_repeated(&mut self, byte: u8, count: usize) -> &mut Self {
+        let buf = self.buf();
+        buf.resize(buf.len() + count, byte);
+        self
+    }
+
+    /// Writes zero bytes until the length of the output is a multiple of `align`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is zero.
+    pub fn write_align_to(&mut self, align: usize) -> &mut Self {
+        assert!(align != 0, "align must not be zero");
+        let rem = self.len() % align;
+        if rem != 0 {
+            self.write_zeros(align - rem);
+        }
+        self
+    }
+
+    /// Overwrites bytes that have already been written, starting at `offset`.
+    ///
+    /// If `offset + bytes.len()` is beyond the end of the output, then this function returns
+    /// `Err(BinaryWriterError::OutOfBounds)` and the output is not modified.
+    pub(crate) fn patch_bytes(&mut self, offset: usize, bytes: &[u8]) -> Result<()> {
+        let Some(dest) = offset
+            .checked_add(bytes.len())
+            .and_then(|end| self.buf().get_mut(offset..end))
+        else {
+            return Err(BinaryWriterError::OutOfBounds);
+        };
+        dest.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Overwrites a `u32` value that has already been written (or reserved) at `offset`.
+    ///
+    /// This is useful for formats that contain fields that are not known until later, such as
+    /// the length of a record. Write a placeholder value, then patch it once the value is known.
+    pub fn patch_u32(&mut self, offset: usize, value: u32) -> Result<()> {
+        self.patch_bytes(offset, &value.to_le_bytes())
     }
 
     /// Writes a small, fixed-size array of bytes.
-    pub fn write_cbytes<const N: usize>(&mut self, value: [u8; N]) {
+    pub fn write_cbytes<const N: usize>(&mut self, value: [u8; N]) -> &mut Self {
         self.write_bytes(&value)
     }
 
+    /// Writes a magic number or signature. This is the same as [`Self::write_bytes`], but it
+    /// documents the intent. Use `BinaryReader::expect_bytes` to check it when reading.
+    pub fn write_magic(&mut self, magic: &[u8]) -> &mut Self {
+        self.write_bytes(magic)
+    }
+
+    /// Writes a fixed-size magic number or signature. This pairs with
+    /// `BinaryReader::read_cbytes::<N>`.
+    pub fn write_cmagic<const N: usize>(&mut self, magic: [u8; N]) -> &mut Self {
+        self.write_cbytes(magic)
+    }
+
     /// Writes a single `u8` value
-    pub fn write_u8(&mut self, value: u8) {
+    pub fn write_u8(&mut self, value: u8) -> &mut Self {
         self.write_bytes(&[value])
     }
 
     /// Writes a single `i8` value
-    pub fn write_i8(&mut self, value: i8) {
+    pub fn write_i8(&mut self, value: i8) -> &mut Self {
         self.write_bytes(&[value as u8])
     }
 
     /// Writes a single `u16` value
-    pub fn write_u16(&mut self, value: u16) {
+    pub fn write_u16(&mut self, value: u16) -> &mut Self {
         self.write_cbytes(value.to_le_bytes())
     }
 
+    /// Writes a 24-bit unsigned integer (3 bytes) in little-endian byte order.
+    ///
+    /// If `value` is larger than `0xFF_FFFF`, then this returns
+    /// `Err(BinaryWriterError::CannotEncode)` and nothing is written.
+    pub fn write_u24(&mut self, value: u32) -> Result<()> {
+        let [b0, b1, b2, b3] = value.to_le_bytes();
+        if b3 != 0 {
+            return Err(BinaryWriterError::CannotEncode);
+        }
+        self.write_cbytes([b0, b1, b2]);
+        Ok(())
+    }
+
+    /// Writes a 24-bit unsigned integer (3 bytes) in big-endian byte order.
+    ///
+    /// If `value` is larger than `0xFF_FFFF`, then this returns
+    /// `Err(BinaryWriterError::CannotEncode)` and nothing is written.
+    pub fn write_u24_be(&mut self, value: u32) -> Result<()> {
+        let [b0, b1, b2, b3] = value.to_be_bytes();
+        if b0 != 0 {
+            return Err(BinaryWriterError::CannotEncode);
+        }
+        self.write_cbytes([b1, b2, b3]);
+        Ok(())
+    }
+
     /// Writes a single `u32` value
-    pub fn write_u32(&mut self, value: u32) {
+    pub fn write_u32(&mut self, value: u32) -> &mut Self {
         self.write_cbytes(value.to_le_bytes())
     }
 
     /// Writes a single `u64` value
-    pub fn write_u64(&mut self, value: u64) {
+    pub fn write_u64(&mut self, value: u64) -> &mut Self {
         self.write_cbytes(value.to_le_bytes())
     }
 
     /// Writes a single `i16` value
-    pub fn write_i16(&mut self, value: i16) {
+    pub fn write_i16(&mut self, value: i16) -> &mut Self {
         self.write_cbytes(value.to_le_bytes())
     }
 
     /// Writes a single `i32` value
-    pub fn write_i32(&mut self, value: i32) {
+    pub fn write_i32(&mut self, value: i32) -> &mut Self {
         self.write_cbytes(value.to_le_bytes())
     }
 
     /// Writes a single `i64` value
-    pub fn write_i64(&mut self, value: i64) {
+    pub fn write_i64(&mut self, value: i64) -> &mut Self {
         self.write_cbytes(value.to_le_bytes())
     }
 
+    /// Writes a slice of `u16` values. Each value is written in little-endian byte order.
+    pub fn write_u16_slice(&mut self, values: &[u16]) -> &mut Self {
+        self.write_le_slice(values, u16::to_le_bytes)
+    }
+
+    /// Writes a slice of `u32` values. Each value is written in little-endian byte order.
+    pub fn write_u32_slice(&mut self, values: &[u32]) -> &mut Self {
+        self.write_le_slice(values, u32::to_le_bytes)
+    }
+
+    /// Writes a slice of `i32` values. Each value is written in little-endian byte order.
+    pub fn write_i32_slice(&mut self, values: &[i32]) -> &mut Self {
+        self.write_le_slice(values, i32::to_le_bytes)
+    }
+
+    /// Writes a slice of `f32` values. Each value is written in little-endian byte order.
+    pub fn write_f32_slice(&mut self, values: &[f32]) -> &mut Self {
+        self.write_le_slice(values, f32::to_le_bytes)
+    }
+
+    /// Writes a slice of `f64` values. Each value is written in little-endian byte order.
+    pub fn write_f64_slice(&mut self, values: &[f64]) -> &mut Self {
+        self.write_le_slice(values, f64::to_le_bytes)
+    }
+
+    /// Writes a slice of values in little-endian byte order. On little-endian targets, the
+    /// in-memory representation is already correct, so the slice is copied as a whole.
+    fn write_le_slice<T, const N: usize>(
+        &mut self,
+        values: &[T],
+        to_le_bytes: impl Fn(T) -> [u8; N],
+    ) -> &mut Self
+    where
+        T: IntoBytes + Immutable + Copy,
+    {
+        if cfg!(target_endian = "little") {
+            self.write_bytes(values.as_bytes());
+        } else {
+            self.buf().reserve(values.len() * N);
+            for &value in values.iter() {
+                self.write_cbytes(to_le_bytes(value));
+            }
+        }
+        self
+    }
+
     /// Encodes an `i32` value using a variable-length encoding.
     ///
     /// Although this function takes `i32` values, applications should avoid using this for
     /// negative values. This function can correctly encode negative values, but most "small"
     /// negative value (e.g. `-10`) will be encoded with the maximum number of bytes, which wastes
     /// space.
-    pub fn write_7bit_encoded_i32(&mut self, value: i32) {
+    ///
+    /// A more compact encoding for negative values (such as zigzag encoding) would not be
+    /// compatible with .NET's `BinaryReader.Read7BitEncodedInt`, so this function always uses the
+    /// same encoding as .NET. Use [`Self::encoded_len_7bit_i32`] to find the encoded size.
+    pub fn write_7bit_encoded_i32(&mut self, value: i32) -> &mut Self {
         const MORE: u8 = 0x80; // bit indicating there are more bits
         const MASK: u8 = 0x7f;
 
@@ -116,6 +439,7 @@ impl BinaryWriter {
         } else {
             self.write_cbytes([w0]);
         }
+        self
     }
 
     /// Encodes an `i64` value using a variable-length encoding.
@@ -124,34 +448,180 @@ impl BinaryWriter {
     /// negative values. This function can correctly encode negative values, but most "small"
     /// negative value (e.g. `-10`) will be encoded with the maximum number of bytes, which wastes
     /// space.
-    pub fn write_7bit_encoded_i64(&mut self, value: i64) {
+    pub fn write_7bit_encoded_i64(&mut self, value: i64) -> &mut Self {
         let mut n: u64 = value as u64;
+        let mut buf = [0u8; 10];
+        let mut len = 0;
 
         loop {
             if n < 0x80 {
-                self.write_u8(n as u8);
+                buf[len] = n as u8;
+                len += 1;
                 break;
             }
-            self.write_u8((n & 0x7f) as u8 | 0x80);
+            buf[len] = (n & 0x7f) as u8 | 0x80;
+            len += 1;
             n >>= 7;
         }
+
+        self.write_bytes(&buf[..len])
+    }
+
+    /// Writes the discriminant (tag) of a tagged union, such as a Rust `enum`, using the 7-bit
+    /// encoding. Small tags are encoded in a single byte. Use `BinaryReader::read_tag` to read it.
+    pub fn write_tag(&mut self, tag: u32) -> &mut Self {
+        self.write_7bit_encoded_i32(tag as i32)
     }
 
     /// Writes a `bool` value. True is encoded as 1. False is encoded as 0.
-    pub fn write_bool(&mut self, value: bool) {
+    pub fn write_bool(&mut self, value: bool) -> &mut Self {
         self.write_u8(value as u8)
     }
 
+    /// Writes a `char` value, encoded as UTF-8. This matches the behavior of .NET's
+    /// `BinaryWriter.Write(char)` when the writer uses its default encoding (UTF-8).
+    ///
+    /// A .NET `char` is a single UTF-16 code unit, so .NET can only write characters in the Basic
+    /// Multilingual Plane this way. This function encodes any `char`, using 1 to 4 bytes.
+    pub fn write_char(&mut self, value: char) -> &mut Self {
+        let mut buf = [0u8; 4];
+        self.write_bytes(value.encode_utf8(&mut buf).as_bytes())
+    }
+
+    /// Writes a `char` value, encoded as UTF-16 in little-endian byte order. This matches the
+    /// behavior of .NET's `BinaryWriter.Write(char)` when the writer uses `Encoding.Unicode`.
+    ///
+    /// Unlike [`Self::write_char`], this writes either 2 bytes (for characters in the Basic
+    /// Multilingual Plane) or 4 bytes (a surrogate pair, for all other characters).
+    pub fn write_char_utf16(&mut self, value: char) -> &mut Self {
+        let mut buf = [0u16; 2];
+        self.write_u16_slice(value.encode_utf16(&mut buf))
+    }
+
+    /// Writes a single ASCII character as one byte.
+    ///
+    /// If `c` is not an ASCII character, then this returns `Err(BinaryWriterError::CannotEncode)`
+    /// and does not write anything.
+    pub fn write_ascii_char(&mut self, c: char) -> Result<()> {
+        if !c.is_ascii() {
+            return Err(BinaryWriterError::CannotEncode);
+        }
+        self.write_u8(c as u8);
+        Ok(())
+    }
+
+    /// Writes a 4-byte `bool` value, such as a Win32 `BOOL`. True is encoded as the `u32` value 1.
+    /// False is encoded as 0.
+    pub fn write_bool32(&mut self, value: bool) -> &mut Self {
+        self.write_u32(value as u32)
+    }
+
     /// Writes an `f32` value. The value is encoded using its 4-byte little-endian in-memory
     /// representation.
-    pub fn write_f32(&mut self, value: f32) {
-        self.write_cbytes(value.to_le_bytes());
+    pub fn write_f32(&mut self, value: f32) -> &mut Self {
+        self.write_cbytes(value.to_le_bytes())
     }
 
-    /// Writes an `f64` value. The value is encoded using its 4-byte little-endian in-memory
+    /// Writes an `f64` value. The value is encoded using its 8-byte little-endian in-memory
     /// representation.
-    pub fn write_f64(&mut self, value: f64) {
-        self.write_cbytes(value.to_le_bytes());
+    pub fn write_f64(&mut self, value: f64) -> &mut Self {
+        self.write_cbytes(value.to_le_bytes())
+    }
+
+    /// Writes a `half::f16` (half-precision float) value. The value is encoded using its 2-byte
+    /// little-endian in-memory representation. This matches .NET's `BinaryWriter.Write(Half)`.
+    #[cfg(feature = "half")]
+    pub fn write_f16(&mut self, value: half::f16) -> &mut Self {
+        self.write_cbytes(value.to_le_bytes())
+    }
+
+    /// Writes a `uuid::Uuid` value using the same encoding as .NET's `System.Guid`.
+    ///
+    /// The output is identical to the output of `Guid.ToByteArray()`. The first three fields of
+    /// the GUID are stored in little-endian byte order.
+    #[cfg(feature = "uuid")]
+    pub fn write_uuid(&mut self, value: &uuid::Uuid) -> &mut Self {
+        self.write_cbytes(value.to_bytes_le())
+    }
+
+    /// Writes the raw representation of a .NET `decimal` value, which is 4 `i32` values in the
+    /// order `lo`, `mid`, `hi`, `flags`. This matches the array returned by .NET's
+    /// `decimal.GetBits` and the encoding used by `BinaryWriter.Write(decimal)`.
+    ///
+    /// The `flags` value is not validated.
+    pub fn write_decimal_bits(&mut self, bits: [i32; 4]) -> &mut Self {
+        for b in bits {
+            self.write_i32(b);
+        }
+        self
+    }
+
+    /// Writes a `rust_decimal::Decimal` value using the same encoding as .NET's `decimal`.
+    ///
+    /// The 96-bit mantissa is stored in `lo`, `mid`, and `hi`. The scale is stored in bits 16 to
+    /// 23 of `flags`, and the sign is stored in bit 31 of `flags`.
+    #[cfg(feature = "rust_decimal")]
+    pub fn write_rust_decimal(&mut self, d: rust_decimal::Decimal) -> &mut Self {
+        let m = d.mantissa().unsigned_abs();
+        let mut flags = d.scale() << 16;
+        if d.is_sign_negative() {
+            flags |= 0x8000_0000;
+        }
+        self.write_decimal_bits([m as i32, (m >> 32) as i32, (m >> 64) as i32, flags as i32])
+    }
+
+    /// Writes a `Duration` as a .NET `TimeSpan`, which is an `i64` count of ticks. Each tick is
+    /// 100 nanoseconds, so any fraction of a tick in `d` is truncated.
+    ///
+    /// If `d` is too long to be represented as a `TimeSpan` (about 29,227 years), then this
+    /// returns `Err(BinaryWriterError::CannotEncode)` and does not write anything.
+    pub fn write_duration_ticks(&mut self, d: core::time::Duration) -> Result<()> {
+        let ticks =
+            i64::try_from(d.as_nanos() / 100).map_err(|_| BinaryWriterError::CannotEncode)?;
+        self.write_i64(ticks);
+        Ok(())
+    }
+
+    /// Writes a `SystemTime` as the `Ticks` value of a .NET `DateTime` in UTC. This is an `i64`
+    /// count of 100-nanosecond ticks since 0001-01-01 00:00:00 (in the proleptic Gregorian
+    /// calendar), so the Unix epoch is 621,355,968,000,000,000 ticks. Any fraction of a tick is
+    /// truncated toward the Unix epoch.
+    ///
+    /// Note that this is the value of `DateTime.Ticks`, not `DateTime.ToBinary()`, which also
+    /// stores the `DateTimeKind` in the top 2 bits. Use `new DateTime(ticks, DateTimeKind.Utc)`
+    /// in .NET to decode it.
+    ///
+    /// If `t` is outside of the range of `DateTime` (years 1 to 9999), then this returns
+    /// `Err(BinaryWriterError::CannotEncode)` and does not write anything.
+    #[cfg(feature = "std")]
+    pub fn write_systemtime_ticks(&mut self, t: std::time::SystemTime) -> Result<()> {
+        let offset = match t.duration_since(std::time::UNIX_EPOCH) {
+            Ok(d) => i64::try_from(d.as_nanos() / 100),
+            Err(e) => i64::try_from(e.duration().as_nanos() / 100).map(|ticks| -ticks),
+        };
+        let ticks = offset
+            .ok()
+            .and_then(|offset| offset.checked_add(UNIX_EPOCH_TICKS))
+            .filter(|ticks| (0..=MAX_DATETIME_TICKS).contains(ticks))
+            .ok_or(BinaryWriterError::CannotEncode)?;
+        self.write_i64(ticks);
+        Ok(())
+    }
+
+    /// Writes an `f32` value using its 4-byte big-endian representation.
+    ///
+    /// .NET's `BinaryWriter` always uses little-endian byte order. This is provided for formats
+    /// that mix .NET-encoded data with big-endian data.
+    pub fn write_f32_be(&mut self, value: f32) -> &mut Self {
+        self.write_cbytes(value.to_be_bytes())
+    }
+
+    /// Writes an `f64` value using its 8-byte big-endian representation.
+    ///
+    /// .NET's `BinaryWriter` always uses little-endian byte order. This is provided for formats
+    /// that mix .NET-encoded data with big-endian data.
+    pub fn write_f64_be(&mut self, value: f64) -> &mut Self {
+        self.write_cbytes(value.to_be_bytes())
     }
 
     /// Writes a UTF-8 string in length-prefixed form.
@@ -162,37 +632,311 @@ impl BinaryWriter {
         Ok(())
     }
 
+    /// Writes a UTF-8 string in length-prefixed form, optionally rejecting strings that contain
+    /// the Unicode replacement character (U+FFFD).
+    ///
+    /// A `&str` is always well-formed, but strings that were converted lossily from malformed
+    /// input (for example, with `String::from_utf16_lossy`) contain U+FFFD where the input was
+    /// invalid. If `reject_replacement` is `true` and `s` contains U+FFFD, then this returns
+    /// `Err(BinaryWriterError::CannotEncode)` and does not write anything. Otherwise, this is
+    /// the same as [`Self::write_utf8_str`].
+    pub fn write_utf8_str_strict(&mut self, s: &str, reject_replacement: bool) -> Result<()> {
+        if reject_replacement && s.contains(char::REPLACEMENT_CHARACTER) {
+            return Err(BinaryWriterError::CannotEncode);
+        }
+        self.write_utf8_str(s)
+    }
+
+    /// Writes an optional UTF-8 string. This writes a `bool` that indicates whether the string is
+    /// present, followed by the length-prefixed string if it is `Some`. This is a common pattern
+    /// for nullable string fields in .NET serializers.
+    ///
+    /// If the string is too long to encode, then this returns
+    /// `Err(BinaryWriterError::CannotEncode)` and nothing is written.
+    pub fn write_option_utf8_str(&mut self, s: Option<&str>) -> Result<()> {
+        let Some(s) = s else {
+            self.write_bool(false);
+            return Ok(());
+        };
+
+        if i32::try_from(s.len()).is_err() {
+            return Err(BinaryWriterError::CannotEncode);
+        }
+        self.write_bool(true);
+        self.write_utf8_str(s)
+    }
+
+    /// Writes a count-prefixed list of UTF-8 strings. The count is encoded as a 7-bit encoded
+    /// `i32`, followed by each string in length-prefixed form. This matches the common encoding
+    /// of `string[]` in .NET serializers.
+    ///
+    /// If the list or any of its strings is too long to encode, then this returns
+    /// `Err(BinaryWriterError::CannotEncode)` and nothing is written.
+    pub fn write_utf8_string_list(&mut self, list: &[&str]) -> Result<()> {
+        let count_i32 = i32::try_from(list.len()).map_err(|_| BinaryWriterError::CannotEncode)?;
+        let saved_len = self.len();
+        self.write_7bit_encoded_i32(count_i32);
+        for s in list.iter() {
+            if let Err(e) = self.write_utf8_str(s) {
+                self.truncate(saved_len);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a count-prefixed list of key/value pairs of UTF-8 strings. The count is encoded as
+    /// a 7-bit encoded `i32`, followed by each key and then its value in length-prefixed form.
+    /// This matches a common encoding of `Dictionary<string, string>`.
+    ///
+    /// If the map or any of its strings is too long to encode, then this returns
+    /// `Err(BinaryWriterError::CannotEncode)` and nothing is written.
+    pub fn write_string_map(&mut self, map: &[(&str, &str)]) -> Result<()> {
+        let count_i32 = i32::try_from(map.len()).map_err(|_| BinaryWriterError::CannotEncode)?;
+        let saved_len = self.len();
+        self.write_7bit_encoded_i32(count_i32);
+        for (key, value) in map.iter() {
+            if let Err(e) = self
+                .write_utf8_str(key)
+                .and_then(|()| self.write_utf8_str(value))
+            {
+                self.truncate(saved_len);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a UTF-8 string without a length prefix. This is for formats where the length is
+    /// stored elsewhere or is fixed. Use `BinaryReader::read_utf8_str_exact` to read it.
+    pub fn write_utf8_str_raw(&mut self, s: &str) -> &mut Self {
+        self.write_bytes(s.as_bytes())
+    }
+
+    /// Writes a UTF-8 string into a fixed-size field of `field_len` bytes, without a length
+    /// prefix. If the string is shorter than the field, then the rest of the field is filled with
+    /// NUL bytes. Use `BinaryReader::read_utf8_str_exact` to read the field, including the padding.
+    ///
+    /// If the string is longer than `field_len` bytes, then this returns
+    /// `Err(BinaryWriterError::CannotEncode)` and does not write anything.
+    pub fn write_utf8_str_padded(&mut self, s: &str, field_len: usize) -> Result<()> {
+        if s.len() > field_len {
+            return Err(BinaryWriterError::CannotEncode);
+        }
+        self.write_bytes(s.as_bytes())
+            .write_zeros(field_len - s.len());
+        Ok(())
+    }
+
     /// Writes a UTF-8 string in length-prefixed form.
     ///
     /// This function does not validate that the input string is well-formed UTF-8.
     pub fn write_utf8_bytes(&mut self, s: &[u8]) -> Result<()> {
-        let len_i32 = i32::try_from(s.len()).map_err(|_| BinaryWriterError::CannotEncode)?;
-        self.write_7bit_encoded_i32(len_i32);
+        self.write_length_prefixed_bytes(s)
+    }
+
+    /// Writes a UTF-8 string in length-prefixed form, but only if it is no longer than `max`
+    /// bytes.
+    ///
+    /// If `s` is longer than `max` bytes, then this returns `Err(BinaryWriterError::CannotEncode)`
+    /// and does not write anything. This function does not validate that the input string is
+    /// well-formed UTF-8.
+    pub fn write_utf8_bytes_max(&mut self, s: &[u8], max: usize) -> Result<()> {
+        if s.len() > max {
+            return Err(BinaryWriterError::CannotEncode);
+        }
+        self.write_utf8_bytes(s)
+    }
+
+    /// Writes a UTF-8 string in length-prefixed form, where the length prefix is a 7-bit encoded
+    /// `i64`, rather than `i32`.
+    ///
+    /// **This is not compatible with .NET's `BinaryWriter`**, which always uses a 7-bit encoded
+    /// `i32` for string lengths. For lengths that are less than 2^31, the encoded length is
+    /// identical, but readers that expect the standard encoding will reject longer lengths.
+    ///
+    /// This function does not validate that the input string is well-formed UTF-8.
+    pub fn write_utf8_bytes_long(&mut self, s: &[u8]) -> Result<()> {
+        let len_i64 = i64::try_from(s.len()).map_err(|_| BinaryWriterError::CannotEncode)?;
+        self.write_7bit_encoded_i64(len_i64);
         self.write_bytes(s);
         Ok(())
     }
 
+    /// Writes a sequence of bytes in length-prefixed form. The length is encoded as a 7-bit
+    /// encoded `i32`.
+    pub fn write_length_prefixed_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let len_i32 = i32::try_from(bytes.len()).map_err(|_| BinaryWriterError::CannotEncode)?;
+        self.write_7bit_encoded_i32(len_i32);
+        self.write_bytes(bytes);
+        Ok(())
+    }
+
+    /// Writes a sequence of bytes, preceded by its length as a `u16` in little-endian byte order.
+    ///
+    /// **This is not a .NET `BinaryWriter` encoding**, but it is common in related formats. If
+    /// the length is greater than `u16::MAX`, then this returns
+    /// `Err(BinaryWriterError::CannotEncode)` and does not write anything.
+    pub fn write_blob_u16_prefixed(&mut self, bytes: &[u8]) -> Result<()> {
+        let len = u16::try_from(bytes.len()).map_err(|_| BinaryWriterError::CannotEncode)?;
+        self.write_u16(len).write_bytes(bytes);
+        Ok(())
+    }
+
+    /// Writes a sequence of bytes, preceded by its length as a `u32` in little-endian byte order.
+    ///
+    /// **This is not a .NET `BinaryWriter` encoding**, but it is common in related formats. If
+    /// the length is greater than `u32::MAX`, then this returns
+    /// `Err(BinaryWriterError::CannotEncode)` and does not write anything.
+    pub fn write_blob_u32_prefixed(&mut self, bytes: &[u8]) -> Result<()> {
+        let len = u32::try_from(bytes.len()).map_err(|_| BinaryWriterError::CannotEncode)?;
+        self.write_u32(len).write_bytes(bytes);
+        Ok(())
+    }
+
     /// Writes a UTF-16 string in length-prefixed form.
     ///
     /// This function does not validate that the input string is well-formed UTF-16.
     pub fn write_utf16_wchars(&mut self, s: &[u16]) -> Result<()> {
-        let s_bytes = s.as_bytes();
-        let len_i32 = i32::try_from(s_bytes.len()).map_err(|_| BinaryWriterError::CannotEncode)?;
+        let len_i32 = BinaryWriter::utf16_len_prefix(s.len())?;
         self.write_7bit_encoded_i32(len_i32);
-        self.write_bytes(s_bytes);
+        self.write_u16_slice(s);
         Ok(())
     }
 
     /// Converts a UTF-8 string into UTF-16 and writes it in length-prefixed form.
-    pub fn write_utf16_encode(&mut self, s: &str) {
+    ///
+    /// If the length of the encoded string (in bytes) cannot be represented as an `i32`, then
+    /// this returns `Err(BinaryWriterError::CannotEncode)` and does not write anything.
+    pub fn write_utf16_encode(&mut self, s: &str) -> Result<()> {
+        self.write_utf16_encode_counted(s)?;
+        Ok(())
+    }
+
+    /// Converts a UTF-8 string into UTF-16 and writes it in length-prefixed form, and returns
+    /// the number of UTF-16 code units that were written (not including the length prefix).
+    ///
+    /// This is the same as [`Self::write_utf16_encode`]. It is useful for formats that store the
+    /// number of code units in a separate field.
+    pub fn write_utf16_encode_counted(&mut self, s: &str) -> Result<usize> {
         let num_utf16_code_units = s.encode_utf16().count();
-        let len_bytes: usize = num_utf16_code_units * 2;
-        self.write_7bit_encoded_i32(len_bytes as i32);
+        let len_i32 = BinaryWriter::utf16_len_prefix(num_utf16_code_units)?;
+        self.write_7bit_encoded_i32(len_i32);
 
-        self.out.reserve(len_bytes);
+        self.buf().reserve(len_i32 as usize);
         for c in s.encode_utf16() {
             self.write_u16(c);
         }
+        Ok(num_utf16_code_units)
+    }
+
+    /// Converts a slice of `char` values into UTF-16 and writes it in length-prefixed form. The
+    /// encoding is the same as [`Self::write_utf16_encode`], but the caller does not need to
+    /// build a `String` first.
+    ///
+    /// Characters outside of the Basic Multilingual Plane are encoded as surrogate pairs, so the
+    /// length prefix counts 4 bytes for each of them.
+    pub fn write_utf16_from_chars(&mut self, chars: &[char]) -> Result<()> {
+        let num_utf16_code_units: usize = chars.iter().map(|c| c.len_utf16()).sum();
+        let len_i32 = BinaryWriter::utf16_len_prefix(num_utf16_code_units)?;
+        self.write_7bit_encoded_i32(len_i32);
+
+        self.buf().reserve(len_i32 as usize);
+        for &c in chars {
+            self.write_char_utf16(c);
+        }
+        Ok(())
+    }
+
+    /// Converts a UTF-16 string into UTF-8 and writes it in length-prefixed form. The encoding is
+    /// the same as [`Self::write_utf8_str`]. This does not allocate an intermediate `String`.
+    ///
+    /// If `wchars` contains unpaired surrogates, then this function returns
+    /// `Err(BinaryWriterError::CannotEncode)` and does not write anything.
+    pub fn write_utf16_as_utf8(&mut self, wchars: &[u16]) -> Result<()> {
+        let mut len_bytes: usize = 0;
+        for c in char::decode_utf16(wchars.iter().copied()) {
+            let c = c.map_err(|_| BinaryWriterError::CannotEncode)?;
+            len_bytes += c.len_utf8();
+        }
+        let len_i32 = i32::try_from(len_bytes).map_err(|_| BinaryWriterError::CannotEncode)?;
+        self.write_7bit_encoded_i32(len_i32);
+
+        self.buf().reserve(len_bytes);
+        for c in char::decode_utf16(wchars.iter().copied()).flatten() {
+            self.write_char(c);
+        }
+        Ok(())
+    }
+
+    /// Writes a string in length-prefixed form, using the Latin-1 (ISO-8859-1) encoding. This
+    /// matches the behavior of a .NET `BinaryWriter` that uses `Encoding.Latin1`.
+    ///
+    /// Each character is encoded as a single byte. If any character in `s` is outside of the
+    /// range U+0000 to U+00FF, then this function returns `Err(BinaryWriterError::CannotEncode)`
+    /// and does not write anything.
+    pub fn write_latin1_str(&mut self, s: &str) -> Result<()> {
+        if s.chars().any(|c| (c as u32) > 0xff) {
+            return Err(BinaryWriterError::CannotEncode);
+        }
+
+        let len = s.chars().count();
+        let len_i32 = i32::try_from(len).map_err(|_| BinaryWriterError::CannotEncode)?;
+        self.write_7bit_encoded_i32(len_i32);
+
+        self.buf().reserve(len);
+        for c in s.chars() {
+            self.write_u8(c as u8);
+        }
+        Ok(())
+    }
+
+    /// Writes a string in length-prefixed form, using the ASCII encoding. This matches the
+    /// behavior of a .NET `BinaryWriter` that uses `Encoding.ASCII`.
+    ///
+    /// Each character is encoded as a single byte. **This encoding is lossy.** Just like .NET's
+    /// `Encoding.ASCII`, any character that is not in the ASCII range (U+0000 to U+007F) is
+    /// replaced with `?`. For example, `"café"` is written as `"caf?"`.
+    pub fn write_ascii_str(&mut self, s: &str) -> Result<()> {
+        let len = s.chars().count();
+        let len_i32 = i32::try_from(len).map_err(|_| BinaryWriterError::CannotEncode)?;
+        self.write_7bit_encoded_i32(len_i32);
+
+        self.buf().reserve(len);
+        for c in s.chars() {
+            self.write_u8(if c.is_ascii() { c as u8 } else { b'?' });
+        }
+        Ok(())
+    }
+
+    /// Writes a null-terminated UTF-8 string. The string is not length-prefixed.
+    ///
+    /// This is not an encoding that .NET's `BinaryWriter` uses, but it is common in interop
+    /// formats. If `s` contains a NUL character, then this function returns
+    /// `Err(BinaryWriterError::CannotEncode)`, because the string could not be decoded correctly.
+    pub fn write_cstr(&mut self, s: &str) -> Result<()> {
+        if s.as_bytes().contains(&0) {
+            return Err(BinaryWriterError::CannotEncode);
+        }
+
+        self.write_bytes(s.as_bytes());
+        self.write_u8(0);
+        Ok(())
+    }
+
+    /// Writes a nested, length-prefixed block. `body` writes the contents of the block into a
+    /// temporary `BinaryWriter`, and then the contents are written to this writer, preceded by
+    /// their length (encoded as a 7-bit encoded `i32`).
+    ///
+    /// This pairs with `BinaryReader::read_subreader`. If `body` fails, then its error is returned
+    /// and nothing is written.
+    pub fn write_subframe<F>(&mut self, body: F) -> Result<()>
+    where
+        F: FnOnce(&mut BinaryWriter) -> Result<()>,
+    {
+        let mut inner = BinaryWriter::new();
+        body(&mut inner)?;
+        self.write_length_prefixed_bytes(&inner.out)
     }
 }
 
@@ -202,6 +946,10 @@ pub enum BinaryWriterError {
     /// Indicates that a value cannot be encoded. This is used for cases where a string or slice
     /// is too large to encode using the variable-length encoding rules.
     CannotEncode,
+
+    /// Indicates that a `patch_*` call specified a range that is outside of the data that has
+    /// been written.
+    OutOfBounds,
 }
 
 impl core::error::Error for BinaryWriterError {}
@@ -210,6 +958,7 @@ impl core::fmt::Display for BinaryWriterError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::CannotEncode => f.write_str("The data cannot be encoded"),
+            Self::OutOfBounds => f.write_str("The range is outside of the data that was written"),
         }
     }
 }